@@ -0,0 +1,87 @@
+//! Compares inserting, removing and filtering by a zero-sized marker component against a
+//! component with data.
+//!
+//! Run with `cargo bench --bench marker`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use shiv::{
+    query::{With, Without},
+    world::{Component, Entity, World},
+};
+
+const ENTITIES: usize = 100_000;
+const ITERATIONS: u32 = 10;
+
+#[derive(Component)]
+struct Position([f32; 2]);
+
+#[derive(Component)]
+struct Marker;
+
+#[derive(Component)]
+struct Tag(#[allow(dead_code)] u64);
+
+#[derive(Default)]
+struct Timings {
+    insert: Duration,
+    remove: Duration,
+    with: Duration,
+    without: Duration,
+}
+
+fn measure<T: Component>(component: impl Fn() -> T) -> Timings {
+    let mut timings = Timings::default();
+
+    for _ in 0..ITERATIONS {
+        let mut world = World::new();
+        let entities = (0..ENTITIES)
+            .map(|i| world.spawn().insert(Position([i as f32; 2])).entity())
+            .collect::<Vec<Entity>>();
+
+        let start = Instant::now();
+        for &entity in entities.iter().step_by(2) {
+            world.entity_mut(entity).insert(component());
+        }
+        timings.insert += start.elapsed();
+
+        let with = world.query_filtered::<&Position, With<T>>();
+        let start = Instant::now();
+        black_box(with.iter(&world).map(|p| p.0[0]).sum::<f32>());
+        timings.with += start.elapsed();
+
+        let without = world.query_filtered::<&Position, Without<T>>();
+        let start = Instant::now();
+        black_box(without.iter(&world).map(|p| p.0[0]).sum::<f32>());
+        timings.without += start.elapsed();
+
+        let start = Instant::now();
+        for &entity in entities.iter().step_by(2) {
+            black_box(world.entity_mut(entity).remove::<T>());
+        }
+        timings.remove += start.elapsed();
+    }
+
+    timings.insert /= ITERATIONS;
+    timings.remove /= ITERATIONS;
+    timings.with /= ITERATIONS;
+    timings.without /= ITERATIONS;
+    timings
+}
+
+fn main() {
+    let marker = measure(|| Marker);
+    let tag = measure(|| Tag(0));
+
+    println!("mark {} of {ENTITIES} entities", ENTITIES / 2);
+    println!("  insert: marker {:?}, tag {:?}", marker.insert, tag.insert);
+    println!("  remove: marker {:?}, tag {:?}", marker.remove, tag.remove);
+    println!("  with: marker {:?}, tag {:?}", marker.with, tag.with);
+    println!(
+        "  without: marker {:?}, tag {:?}",
+        marker.without, tag.without
+    );
+}
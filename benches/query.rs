@@ -0,0 +1,73 @@
+//! Measures tight query loops, where the per-call cost of initializing the fetch dominates.
+//!
+//! Run with `cargo bench --bench query`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use shiv::world::{Component, Entity, World};
+
+const ENTITIES: usize = 1_000;
+const ITERATIONS: u32 = 1_000;
+
+#[derive(Component)]
+struct Position([f32; 2]);
+
+#[derive(Component)]
+struct Velocity([f32; 2]);
+
+fn world() -> (World, Vec<Entity>) {
+    let mut world = World::new();
+
+    let entities = (0..ENTITIES)
+        .map(|i| {
+            let mut entity = world.spawn();
+            entity.insert(Position([i as f32; 2]));
+            entity.insert(Velocity([1.0; 2]));
+            entity.entity()
+        })
+        .collect();
+
+    (world, entities)
+}
+
+fn iterate(world: &mut World) -> Duration {
+    let mut query = world.query::<(&mut Position, &Velocity)>();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for (mut position, velocity) in query.iter_mut(world) {
+            position.0[0] += velocity.0[0];
+            position.0[1] += velocity.0[1];
+        }
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn get(world: &mut World, entities: &[Entity]) -> Duration {
+    let query = world.query::<(&Position, &Velocity)>();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for &entity in entities {
+            let (position, velocity) = query.get(world, entity).unwrap();
+            black_box(position.0[0] + velocity.0[0]);
+        }
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let (mut world, entities) = world();
+
+    let iterate = iterate(&mut world);
+    let get = get(&mut world, &entities);
+
+    println!("query {ENTITIES} entities");
+    println!("  iter_mut: {iterate:?}");
+    println!("  get:      {get:?}");
+}
@@ -0,0 +1,56 @@
+//! Compares spawning entities one by one against [`World::spawn_batch`].
+//!
+//! Run with `cargo bench --bench spawn`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use shiv::world::{Component, World};
+
+const ENTITIES: usize = 100_000;
+const ITERATIONS: u32 = 10;
+
+#[derive(Component)]
+struct Position([f32; 2]);
+
+#[derive(Component)]
+struct Velocity([f32; 2]);
+
+fn bundles() -> impl Iterator<Item = (Position, Velocity)> {
+    (0..ENTITIES).map(|i| (Position([i as f32; 2]), Velocity([1.0; 2])))
+}
+
+fn measure(spawn: impl Fn(&mut World)) -> Duration {
+    let mut total = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let mut world = World::new();
+
+        let start = Instant::now();
+        spawn(&mut world);
+        total += start.elapsed();
+
+        let query = world.query::<(&Position, &Velocity)>();
+        black_box(query.iter(&world).map(|(p, v)| p.0[0] + v.0[1]).sum::<f32>());
+    }
+
+    total / ITERATIONS
+}
+
+fn main() {
+    let naive = measure(|world| {
+        for bundle in bundles() {
+            world.spawn().insert(bundle);
+        }
+    });
+
+    let batch = measure(|world| {
+        world.spawn_batch(bundles());
+    });
+
+    println!("spawn {ENTITIES} entities");
+    println!("  naive: {naive:?}");
+    println!("  batch: {batch:?}");
+}
@@ -0,0 +1,65 @@
+//! Compares iterating a column fragmented by removals against the same column after
+//! [`World::optimize_storage`].
+//!
+//! Run with `cargo bench --bench storage`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use shiv::world::{Component, World};
+
+const ENTITIES: usize = 100_000;
+const ITERATIONS: u32 = 100;
+
+#[derive(Component)]
+struct Position([f32; 4]);
+
+fn fragmented_world() -> World {
+    let mut world = World::new();
+
+    let entities = (0..ENTITIES)
+        .map(|i| world.spawn().insert(Position([i as f32; 4])).entity())
+        .collect::<Vec<_>>();
+
+    // removing and reinserting moves components to the back of the column, pseudo-randomly
+    // shuffling the column relative to entity order
+    let mut seed = 0x2545_f491_u32;
+    for &entity in &entities {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+
+        if seed & 1 == 0 {
+            let position = world.entity_mut(entity).remove::<Position>().unwrap();
+            world.entity_mut(entity).insert(position);
+        }
+    }
+
+    world
+}
+
+fn iterate(world: &mut World) -> Duration {
+    let query = world.query::<&Position>();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let sum = query.iter(world).map(|p| p.0[0]).sum::<f32>();
+        black_box(sum);
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let mut world = fragmented_world();
+    let fragmented = iterate(&mut world);
+
+    world.optimize_storage();
+    let sorted = iterate(&mut world);
+
+    println!("iterate {ENTITIES} entities");
+    println!("  fragmented: {fragmented:?}");
+    println!("  sorted:     {sorted:?}");
+}
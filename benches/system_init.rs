@@ -0,0 +1,101 @@
+//! Counts the allocations made initializing a system with 20 parameters.
+//!
+//! System access is grown to fit every registered component before the parameters are
+//! initialized, so registering the components up front lets the access be allocated once.
+//! Components registered during initialization grow the access one parameter at a time, and
+//! allocate their own metadata as well.
+//!
+//! Run with `cargo bench --bench system_init`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System as SystemAllocator},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use shiv::{
+    query::Query,
+    system::{IntoSystem, System},
+    world::{Component, World},
+};
+
+const ITERATIONS: u32 = 1_000;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts allocations and reallocations, forwarding them to the system allocator.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { SystemAllocator.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { SystemAllocator.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { SystemAllocator.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+macro_rules! components {
+    ($($component:ident),*) => {
+        $(
+            #[derive(Component)]
+            struct $component;
+        )*
+
+        #[allow(clippy::too_many_arguments)]
+        fn system($(_: Query<&$component>),*) {}
+
+        fn register_components(world: &mut World) {
+            $(world.init_component::<$component>();)*
+        }
+    };
+}
+
+components!(
+    C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10, C11, C12, C13, C14, C15, C16, C17, C18, C19
+);
+
+/// Returns the average number of allocations and time spent initializing `system`.
+fn measure(register_first: bool) -> (usize, Duration) {
+    let mut allocations = 0;
+    let mut total = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let mut world = World::new();
+        if register_first {
+            register_components(&mut world);
+        }
+
+        let mut system = system.into_system();
+
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        system.init(&mut world);
+        total += start.elapsed();
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+        black_box(&system);
+    }
+
+    (allocations / ITERATIONS as usize, total / ITERATIONS)
+}
+
+fn main() {
+    let (lazy_allocations, lazy) = measure(false);
+    let (eager_allocations, eager) = measure(true);
+
+    println!("initialize a system with 20 parameters");
+    println!("  registered during init: {lazy_allocations} allocations, {lazy:?}");
+    println!("  registered up front: {eager_allocations} allocations, {eager:?}");
+}
@@ -1,13 +1,120 @@
 use quote::quote;
-use syn::{DeriveInput, Path};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    DeriveInput, Ident, LitStr, Path, Token,
+};
+
+enum AttributeValue {
+    Path(Path),
+    Str(LitStr),
+}
+
+struct ComponentAttribute {
+    name: Ident,
+    value: AttributeValue,
+}
+
+impl Parse for ComponentAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        let value = if input.peek(LitStr) {
+            AttributeValue::Str(input.parse()?)
+        } else {
+            AttributeValue::Path(input.parse()?)
+        };
+
+        Ok(Self { name, value })
+    }
+}
+
+fn expect_path(name: &Ident, value: AttributeValue) -> syn::Result<Path> {
+    match value {
+        AttributeValue::Path(path) => Ok(path),
+        AttributeValue::Str(lit) => {
+            let message = format!("expected a path for `{}`", name);
+            Err(syn::Error::new(lit.span(), message))
+        }
+    }
+}
+
+fn parse_storage(shiv: &Path, name: &Ident, value: AttributeValue) -> syn::Result<Path> {
+    let lit = match value {
+        AttributeValue::Str(lit) => lit,
+        AttributeValue::Path(path) => {
+            let message = format!("expected a string for `{}`", name);
+            return Err(syn::Error::new_spanned(path, message));
+        }
+    };
+
+    match lit.value().as_str() {
+        "Dense" => Ok(syn::parse_quote!(#shiv::storage::DenseStorage)),
+        "SparseSet" => Ok(syn::parse_quote!(#shiv::storage::SparseStorage)),
+        _ => {
+            let message = "unknown storage, expected `Dense` or `SparseSet`";
+            Err(syn::Error::new(lit.span(), message))
+        }
+    }
+}
 
 pub fn derive_component(input: DeriveInput, shiv: Path) -> proc_macro2::TokenStream {
     let name = input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
+    let mut hooks = Vec::new();
+    let mut storage: Path = syn::parse_quote!(#shiv::storage::DenseStorage);
+    for attr in input.attrs.iter() {
+        if !attr.path.is_ident("component") {
+            continue;
+        }
+
+        let parser = Punctuated::<ComponentAttribute, Token![,]>::parse_terminated;
+        let attributes = match attr.parse_args_with(parser) {
+            Ok(attributes) => attributes,
+            Err(err) => return err.to_compile_error(),
+        };
+
+        for ComponentAttribute { name, value } in attributes {
+            let result = match name.to_string().as_str() {
+                "on_insert" => expect_path(&name, value).map(|value| {
+                    hooks.push(quote!(hooks.on_insert(#value);));
+                }),
+                "on_replace" => expect_path(&name, value).map(|value| {
+                    hooks.push(quote!(hooks.on_replace(#value);));
+                }),
+                "storage" => parse_storage(&shiv, &name, value).map(|value| {
+                    storage = value;
+                }),
+                _ => {
+                    let message = format!("unknown component attribute `{}`", name);
+                    Err(syn::Error::new(name.span(), message))
+                }
+            };
+
+            if let Err(err) = result {
+                return err.to_compile_error();
+            }
+        }
+    }
+
+    let register_hooks = if hooks.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            #[inline]
+            fn register_hooks(hooks: &mut #shiv::world::ComponentHooks) {
+                #(#hooks)*
+            }
+        }
+    };
+
     quote! {
         impl #impl_generics #shiv::world::Component for #name #ty_generics #where_clause {
-            type Storage = #shiv::storage::DenseStorage;
+            type Storage = #storage;
+
+            #register_hooks
         }
     }
 }
@@ -13,7 +13,7 @@ macro_rules! implement {
             ::std::convert::From::from($crate::system_param::derive_system_param(input, $path))
         }
 
-        #[proc_macro_derive(Component)]
+        #[proc_macro_derive(Component, attributes(component))]
         pub fn derive_component(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             let input = $crate::syn::parse_macro_input!(input as $crate::syn::DeriveInput);
             ::std::convert::From::from($crate::component::derive_component(input, $path))
@@ -0,0 +1,54 @@
+//! Compares `GlobalTransform * Transform` with `GlobalTransform::mul_transform` when
+//! propagating down a deep hierarchy.
+//!
+//! Run with `cargo bench -p shiv-transform --bench propagate`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use glam::Vec3;
+use shiv_transform::{GlobalTransform, Transform};
+
+const DEPTH: usize = 10_000;
+const ITERATIONS: u32 = 100;
+
+fn hierarchy() -> Vec<Transform> {
+    (0..DEPTH)
+        .map(|i| {
+            Transform::from_xyz(i as f32, 1.0, -1.0)
+                .with_rotation_y(i as f32 * 0.01)
+                .with_scale(Vec3::splat(1.0001))
+        })
+        .collect()
+}
+
+fn propagate(
+    transforms: &[Transform],
+    mul: impl Fn(GlobalTransform, &Transform) -> GlobalTransform,
+) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut global = GlobalTransform::IDENTITY;
+
+        for transform in transforms {
+            global = mul(global, black_box(transform));
+        }
+
+        black_box(global);
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let transforms = hierarchy();
+
+    let generic = propagate(&transforms, |parent, child| parent * *child);
+    let specialized = propagate(&transforms, |parent, child| parent.mul_transform(child));
+
+    println!("propagate {DEPTH} transforms");
+    println!("  mul:           {generic:?}");
+    println!("  mul_transform: {specialized:?}");
+}
@@ -1,6 +1,6 @@
 use std::ops::{Mul, MulAssign};
 
-use glam::{Affine3A, Mat3, Mat3A, Mat4, Quat, Vec3, Vec3A};
+use glam::{Affine3A, EulerRot, Mat3, Mat3A, Mat4, Quat, Vec3, Vec3A};
 use shiv::{bundle::Bundle, world::Component};
 
 #[derive(Clone, Copy, Debug, Default, Bundle)]
@@ -60,6 +60,39 @@ impl Transform {
         }
     }
 
+    #[inline]
+    pub fn from_rotation_x(angle: f32) -> Self {
+        Self::from_rotation(Quat::from_rotation_x(angle))
+    }
+
+    #[inline]
+    pub fn from_rotation_y(angle: f32) -> Self {
+        Self::from_rotation(Quat::from_rotation_y(angle))
+    }
+
+    #[inline]
+    pub fn from_rotation_z(angle: f32) -> Self {
+        Self::from_rotation(Quat::from_rotation_z(angle))
+    }
+
+    /// Creates a transform rotated by the euler angles `a`, `b` and `c`.
+    ///
+    /// The rotations are intrinsic and applied in the order given by `order`,
+    /// e.g. [`EulerRot::XYZ`] rotates by `a` around X, then by `b` around the new Y
+    /// and finally by `c` around the new Z.
+    #[inline]
+    pub fn from_euler(order: EulerRot, a: f32, b: f32, c: f32) -> Self {
+        Self::from_rotation(Quat::from_euler(order, a, b, c))
+    }
+
+    /// Creates a transform rotated by `angle` around `axis`.
+    ///
+    /// `axis` must be normalized.
+    #[inline]
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        Self::from_rotation(Quat::from_axis_angle(axis, angle))
+    }
+
     #[inline]
     pub const fn from_scale(scale: Vec3) -> Self {
         Self {
@@ -86,6 +119,37 @@ impl Transform {
         self
     }
 
+    #[inline]
+    pub fn with_rotation_x(self, angle: f32) -> Self {
+        self.with_rotation(Quat::from_rotation_x(angle))
+    }
+
+    #[inline]
+    pub fn with_rotation_y(self, angle: f32) -> Self {
+        self.with_rotation(Quat::from_rotation_y(angle))
+    }
+
+    #[inline]
+    pub fn with_rotation_z(self, angle: f32) -> Self {
+        self.with_rotation(Quat::from_rotation_z(angle))
+    }
+
+    /// Sets the rotation to the euler angles `a`, `b` and `c`.
+    ///
+    /// See [`Transform::from_euler`] for the rotation order.
+    #[inline]
+    pub fn with_euler(self, order: EulerRot, a: f32, b: f32, c: f32) -> Self {
+        self.with_rotation(Quat::from_euler(order, a, b, c))
+    }
+
+    /// Sets the rotation to `angle` around `axis`.
+    ///
+    /// `axis` must be normalized.
+    #[inline]
+    pub fn with_axis_angle(self, axis: Vec3, angle: f32) -> Self {
+        self.with_rotation(Quat::from_axis_angle(axis, angle))
+    }
+
     #[inline]
     pub const fn with_scale(mut self, scale: Vec3) -> Self {
         self.scale = scale;
@@ -167,6 +231,23 @@ impl Transform {
         self.rotate(Quat::from_rotation_z(angle));
     }
 
+    /// Rotates the translation of `self` by `rotation` around `point`, without changing the
+    /// rotation of `self`.
+    #[inline]
+    pub fn translate_around(&mut self, point: Vec3, rotation: Quat) {
+        self.translation = point + rotation * (self.translation - point);
+    }
+
+    /// Rotates `self` by `rotation` around `point`, changing both the translation and the
+    /// rotation.
+    ///
+    /// Unlike [`Transform::rotate`], `rotation` is relative to the parent, not to `self`.
+    #[inline]
+    pub fn rotate_around(&mut self, point: Vec3, rotation: Quat) {
+        self.translate_around(point, rotation);
+        self.rotation = rotation * self.rotation;
+    }
+
     #[inline]
     pub fn look_at(&mut self, forward: Vec3, up: Vec3) {
         let right = up.cross(forward).normalize();
@@ -203,6 +284,25 @@ impl Transform {
             scale: inv_scale,
         }
     }
+
+    /// Returns true if the translation, rotation and scale of `self` and `other` are all
+    /// within `max_abs_diff` of each other.
+    ///
+    /// Useful for comparing transforms that are subject to floating point rounding. Rotations
+    /// `q` and `-q` represent the same rotation, so they're considered equal.
+    #[inline]
+    pub fn abs_diff_eq(&self, other: Self, max_abs_diff: f32) -> bool {
+        let other_rotation = if self.rotation.dot(other.rotation) < 0.0 {
+            -other.rotation
+        } else {
+            other.rotation
+        };
+
+        self.translation
+            .abs_diff_eq(other.translation, max_abs_diff)
+            && self.rotation.abs_diff_eq(other_rotation, max_abs_diff)
+            && self.scale.abs_diff_eq(other.scale, max_abs_diff)
+    }
 }
 
 impl Mul<Vec3> for Transform {
@@ -362,6 +462,72 @@ impl GlobalTransform {
         let rotation_scale = Mat4::from_mat3(self.matrix);
         translation * rotation_scale
     }
+
+    /// Decomposes `self` into scale, rotation and translation.
+    ///
+    /// If `self` is mirrored, i.e. has a negative determinant, the x scale will be negative.
+    /// The matrix must not contain shear for the result to be meaningful.
+    #[inline]
+    pub fn to_scale_rotation_translation(&self) -> (Vec3, Quat, Vec3) {
+        let det = self.matrix.determinant();
+
+        let scale = Vec3::new(
+            self.matrix.x_axis.length() * det.signum(),
+            self.matrix.y_axis.length(),
+            self.matrix.z_axis.length(),
+        );
+
+        let inv_scale = scale.recip();
+        let rotation = Quat::from_mat3(&Mat3::from_cols(
+            self.matrix.x_axis * inv_scale.x,
+            self.matrix.y_axis * inv_scale.y,
+            self.matrix.z_axis * inv_scale.z,
+        ));
+
+        (scale, rotation.normalize(), self.translation)
+    }
+
+    /// Computes the [`Transform`] equivalent to `self`, see
+    /// [`GlobalTransform::to_scale_rotation_translation`].
+    #[inline]
+    pub fn compute_transform(&self) -> Transform {
+        let (scale, rotation, translation) = self.to_scale_rotation_translation();
+
+        Transform {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Computes the [`GlobalTransform`] of a child with the local transform `child`.
+    ///
+    /// This is equivalent to `self * child`, but scales the rotation matrix directly instead of
+    /// multiplying by a scale matrix, and is used by [`transform_system`](crate::transform_system)
+    /// for propagation.
+    #[inline]
+    pub fn mul_transform(&self, child: &Transform) -> GlobalTransform {
+        let rotation = Mat3::from_quat(child.rotation);
+        let rotation_scale = Mat3::from_cols(
+            rotation.x_axis * child.scale.x,
+            rotation.y_axis * child.scale.y,
+            rotation.z_axis * child.scale.z,
+        );
+
+        Self {
+            translation: self.matrix * child.translation + self.translation,
+            matrix: self.matrix * rotation_scale,
+        }
+    }
+
+    /// Returns true if the translation and matrix of `self` and `other` are all within
+    /// `max_abs_diff` of each other.
+    #[inline]
+    pub fn abs_diff_eq(&self, other: Self, max_abs_diff: f32) -> bool {
+        self.translation
+            .abs_diff_eq(other.translation, max_abs_diff)
+            && self.matrix.abs_diff_eq(other.matrix, max_abs_diff)
+    }
 }
 
 impl From<Transform> for GlobalTransform {
@@ -420,3 +586,106 @@ impl MulAssign for GlobalTransform {
         *self = *self * rhs;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use glam::{EulerRot, Quat, Vec3};
+
+    use super::{GlobalTransform, Transform};
+
+    #[test]
+    fn rotate_around() {
+        let point = Vec3::new(1.0, 2.0, 0.0);
+        let rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let start = Transform::from_xyz(2.0, 2.0, 0.0).with_rotation_x(0.5);
+
+        let mut translated = start;
+        translated.translate_around(point, rotation);
+        let expected = Transform::from_xyz(1.0, 2.0, -1.0).with_rotation_x(0.5);
+        assert!(translated.abs_diff_eq(expected, 1e-5));
+
+        let mut rotated = start;
+        rotated.rotate_around(point, rotation);
+        let expected = expected.with_rotation(rotation * start.rotation);
+        assert!(rotated.abs_diff_eq(expected, 1e-5));
+    }
+
+    #[test]
+    fn decompose_round_trip() {
+        let transform = Transform {
+            translation: Vec3::new(1.0, -2.0, 3.0),
+            rotation: Quat::from_euler(glam::EulerRot::YXZ, 0.4, -1.2, 2.1),
+            scale: Vec3::new(2.0, 0.5, 3.0),
+        };
+
+        let decomposed = GlobalTransform::from(transform).compute_transform();
+
+        assert!(decomposed.abs_diff_eq(transform, 1e-5));
+    }
+
+    #[test]
+    fn abs_diff_eq_negated_rotation() {
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0).with_rotation_x(0.5);
+        let negated = transform.with_rotation(-transform.rotation);
+
+        assert!(transform.abs_diff_eq(negated, 1e-5));
+        assert!(!transform.abs_diff_eq(transform.with_rotation_x(-0.5), 1e-5));
+    }
+
+    #[test]
+    fn decompose_mirrored() {
+        let transform = Transform::from_scale(Vec3::new(-2.0, 1.0, 1.0));
+        let global = GlobalTransform::from(transform);
+
+        let (scale, rotation, _) = global.to_scale_rotation_translation();
+        assert!(scale.abs_diff_eq(Vec3::new(-2.0, 1.0, 1.0), 1e-5));
+        assert!(rotation.abs_diff_eq(Quat::IDENTITY, 1e-5));
+
+        let round_trip = GlobalTransform::from(global.compute_transform());
+        assert!(round_trip.abs_diff_eq(global, 1e-5));
+    }
+
+    #[test]
+    fn euler_order() {
+        let transform = Transform::from_euler(EulerRot::XYZ, 0.3, -0.7, 1.1);
+        let intrinsic =
+            Quat::from_rotation_x(0.3) * Quat::from_rotation_y(-0.7) * Quat::from_rotation_z(1.1);
+        assert!(transform.rotation.abs_diff_eq(intrinsic, 1e-5));
+
+        let axis = Vec3::new(1.0, 2.0, -1.0).normalize();
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0).with_axis_angle(axis, 0.5);
+        assert_eq!(transform.translation, Vec3::new(1.0, 2.0, 3.0));
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_axis_angle(axis, 0.5), 1e-5));
+    }
+
+    #[test]
+    fn mul_transform() {
+        let parent = GlobalTransform::from(
+            Transform::from_xyz(1.0, -2.0, 0.5)
+                .with_rotation_y(0.7)
+                .with_scale(Vec3::new(2.0, 1.0, 0.5)),
+        );
+        let child = Transform::from_xyz(0.0, 2.0, -1.0)
+            .with_rotation_x(-1.3)
+            .with_scale(Vec3::new(-1.0, 3.0, 1.0));
+
+        assert!(parent
+            .mul_transform(&child)
+            .abs_diff_eq(parent * GlobalTransform::from(child), 1e-5));
+    }
+
+    #[test]
+    fn compose_global() {
+        let parent = Transform::from_xyz(1.0, 0.0, 0.0).with_rotation_y(0.5);
+        let child = Transform::from_xyz(0.0, 2.0, -1.0).with_rotation_x(-1.3);
+
+        let global = GlobalTransform::from(parent) * child;
+        assert!(global.abs_diff_eq(GlobalTransform::from(parent * child), 1e-5));
+        assert!(global.compute_transform().abs_diff_eq(parent * child, 1e-5));
+
+        let moved = GlobalTransform::from(parent * child.with_rotation_x(-1.2));
+        assert!(!global.abs_diff_eq(moved, 1e-5));
+    }
+}
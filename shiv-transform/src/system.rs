@@ -73,7 +73,7 @@ fn propagate_recursive(
 
     changed |= transform_changed;
     if changed {
-        *global_transform = parent * transform;
+        *global_transform = parent.mul_transform(&transform);
     }
 
     let global_transform = global_transform.clone();
@@ -12,11 +12,24 @@ use crate::{
 use ahash::HashMap;
 pub use shiv_macro::Bundle;
 
+/// A set of components that can be inserted into or removed from an entity together.
+///
+/// Implemented for every [`Component`] and for tuples of up to 15 components, larger bundles
+/// can be built by deriving `Bundle` on a struct.
+///
+/// # Safety
+/// - [`Bundle::components`] must return the ids of every component in the bundle, in the same
+///   order as [`Bundle::get_components`] yields them.
 pub unsafe trait Bundle: Send + Sync + 'static {
     type Iter: Iterator<Item = *mut u8>;
 
     fn components(components: &mut Components) -> Vec<ComponentId>;
 
+    /// Returns pointers to each component of `bundle`.
+    ///
+    /// # Safety
+    /// - `bundle` must point to a valid `Self`.
+    /// - The components are moved out of `bundle`, so it must not be dropped afterwards.
     unsafe fn get_components(bundle: *mut Self) -> Self::Iter;
 }
 
@@ -34,9 +47,42 @@ unsafe impl<T: Component> Bundle for T {
     }
 }
 
+macro_rules! impl_bundle {
+    (@one $ident:ident) => {
+        1
+    };
+    (@ $($ident:ident),*) => {
+        #[allow(non_snake_case, unused, clippy::unused_unit)]
+        unsafe impl<$($ident: Component),*> Bundle for ($($ident,)*) {
+            type Iter = std::array::IntoIter<*mut u8, { 0 $(+ impl_bundle!(@one $ident))* }>;
+
+            #[inline]
+            fn components(components: &mut Components) -> Vec<ComponentId> {
+                vec![$(components.init_component::<$ident>()),*]
+            }
+
+            #[inline]
+            unsafe fn get_components(bundle: *mut Self) -> Self::Iter {
+                let ($($ident,)*) = unsafe { &mut *bundle };
+                [$($ident as *mut $ident as *mut u8),*].into_iter()
+            }
+        }
+    };
+    ($start:ident $(,$ident:ident)*) => {
+        impl_bundle!(@ $start $(,$ident)*);
+        impl_bundle!($($ident),*);
+    };
+    () => {
+        impl_bundle!(@);
+    };
+}
+
+impl_bundle!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+
 #[derive(Clone, Debug)]
 pub struct BundleInfo {
     component_ids: Vec<ComponentId>,
+    has_hooks: bool,
 }
 
 impl BundleInfo {
@@ -54,7 +100,26 @@ impl BundleInfo {
             }
         }
 
-        Self { component_ids }
+        let has_hooks = component_ids.iter().any(|&id| {
+            let info = components.get(id).unwrap();
+            !info.hooks().is_empty()
+        });
+
+        Self {
+            component_ids,
+            has_hooks,
+        }
+    }
+
+    #[inline]
+    pub fn component_ids(&self) -> &[ComponentId] {
+        &self.component_ids
+    }
+
+    /// Returns `true` if any component in the bundle has [`ComponentHooks`](crate::world::ComponentHooks).
+    #[inline]
+    pub fn has_hooks(&self) -> bool {
+        self.has_hooks
     }
 
     /// # Safety
@@ -78,6 +143,10 @@ impl BundleInfo {
                     let storage = storages.dense.get_or_init(info);
                     unsafe { storage.insert(entity, data, change_tick) };
                 }
+                StorageType::SparseSet => {
+                    let storage = storages.sparse.get_or_init(info);
+                    unsafe { storage.insert(entity, data, change_tick) };
+                }
                 _ => unreachable!(),
             }
         }
@@ -111,6 +180,10 @@ impl BundleInfo {
                     let storage = storages.dense.get_mut(component_id)?;
                     unsafe { storage.remove_unchecked(entity, data) };
                 }
+                StorageType::SparseSet => {
+                    let storage = storages.sparse.get_mut(component_id)?;
+                    unsafe { storage.remove_unchecked(entity, data) };
+                }
                 _ => unreachable!(),
             }
         }
@@ -1,4 +1,12 @@
 //! Types that detect changes.
+//!
+//! Change ticks are `u32`s that wrap around. Comparisons are done relative to the current change
+//! tick, so they stay correct across the wrap as long as no tick is older than
+//! [`MAX_CHANGE_AGE`]. [`World::check_change_ticks`](crate::world::World::check_change_ticks)
+//! clamps every stored tick to at most [`MAX_CHANGE_AGE`], and must be called at least once every
+//! [`CHECK_TICK_THRESHOLD`] ticks, which [`Schedule::run_once`](crate::schedule::Schedule::run_once)
+//! and [`World::clear_trackers`](crate::world::World::clear_trackers) do automatically. A clamped
+//! tick is never reported as changed or added.
 
 use std::ops::{Deref, DerefMut};
 
@@ -76,6 +84,21 @@ impl<'w, T> Mut<'w, T> {
     pub fn get_mut_unchecked(this: &mut Self) -> &mut T {
         this.value
     }
+
+    /// Projects `self` onto a part of the value returned by `f`, sharing the same change ticks.
+    ///
+    /// Calling `f` doesn't mark the value as changed, only mutably dereferencing the returned
+    /// [`Mut`] does. If `f` returns `None`, the ticks are left untouched.
+    #[inline]
+    pub fn filter_map_unchanged<U>(
+        self,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Mut<'w, U>> {
+        Some(Mut {
+            value: f(self.value)?,
+            ticks: self.ticks,
+        })
+    }
 }
 
 impl<'w, T> Deref for Mut<'w, T> {
@@ -97,7 +120,12 @@ impl<'w, T> DerefMut for Mut<'w, T> {
 }
 
 /// Change detection ticks for a single component or resource.
-#[derive(Clone, Copy, Debug)]
+///
+/// Both ticks are values of [`World::change_tick`](crate::world::World::change_tick) at the time
+/// the value was added or last changed. A system sees a value as added or changed if the tick is
+/// newer than the system's last change tick, see [`ChangeTicks::is_added`] and
+/// [`ChangeTicks::is_changed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ChangeTicks {
     added: u32,
     changed: u32,
@@ -135,6 +163,32 @@ impl ChangeTicks {
         }
     }
 
+    /// Creates a new [`ChangeTicks`] from separate `added` and `changed` ticks.
+    ///
+    /// Useful for restoring change state, e.g. when loading a scene.
+    #[inline]
+    pub const fn from_ticks(added: u32, changed: u32) -> Self {
+        Self { added, changed }
+    }
+
+    /// Returns the tick `self` was added at.
+    #[inline]
+    pub const fn added(&self) -> u32 {
+        self.added
+    }
+
+    /// Returns the tick `self` was last changed at.
+    #[inline]
+    pub const fn changed(&self) -> u32 {
+        self.changed
+    }
+
+    /// Marks `self` as added.
+    #[inline]
+    pub fn set_added(&mut self, change_tick: u32) {
+        self.added = change_tick;
+    }
+
     /// Marks `self` as changed.
     #[inline]
     pub fn set_changed(&mut self, change_tick: u32) {
@@ -152,7 +206,7 @@ impl ChangeTicks {
         let age = change_tick.wrapping_sub(*tick);
 
         if age > MAX_CHANGE_AGE {
-            *tick = change_tick;
+            *tick = change_tick.wrapping_sub(MAX_CHANGE_AGE);
         }
     }
 }
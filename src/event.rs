@@ -1,5 +1,7 @@
 use std::{
+    any::{Any, TypeId},
     cmp::Ordering,
+    collections::{vec_deque, VecDeque},
     hash::{Hash, Hasher},
     marker::PhantomData,
     mem,
@@ -7,12 +9,15 @@ use std::{
 };
 
 use crate::{
-    schedule::{SystemLabel, SystemLabelId},
+    hash_map::HashMap,
+    query::{Query, ReadOnlyWorldQuery, WorldQuery},
+    schedule::{ShouldRun, SystemLabel, SystemLabelId},
     system::{
-        Local, LocalState, ReadOnlySystemParamFetch, Res, ResMut, ResMutInit, ResMutState,
-        ResState, SystemMeta, SystemParam, SystemParamFetch, SystemParamState,
+        BoxedSystem, IntoSystem, Local, LocalState, ReadOnlySystemParamFetch, Res, ResMut,
+        ResMutInit, ResMutState, ResState, System, SystemMeta, SystemParam, SystemParamFetch,
+        SystemParamState,
     },
-    world::World,
+    world::{Entity, World},
 };
 
 /// [`Event`]s are used to communicate between systems.
@@ -93,22 +98,31 @@ struct EventInstance<E: Event> {
 
 #[derive(Debug)]
 struct EventSequence<E: Event> {
-    events: Vec<EventInstance<E>>,
+    events: VecDeque<EventInstance<E>>,
     start_event_count: usize,
 }
 
+impl<E: Event> EventSequence<E> {
+    /// Iterates the events with an id of at least `event_count`.
+    #[inline]
+    fn since(&self, event_count: usize) -> vec_deque::Iter<'_, EventInstance<E>> {
+        let index = event_count.saturating_sub(self.start_event_count);
+        self.events.range(usize::min(index, self.events.len())..)
+    }
+}
+
 impl<E: Event> Default for EventSequence<E> {
     #[inline]
     fn default() -> Self {
         EventSequence {
-            events: Vec::new(),
+            events: VecDeque::new(),
             start_event_count: 0,
         }
     }
 }
 
 impl<E: Event> Deref for EventSequence<E> {
-    type Target = Vec<EventInstance<E>>;
+    type Target = VecDeque<EventInstance<E>>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -162,6 +176,7 @@ pub struct Events<E: Event> {
     events_a: EventSequence<E>,
     events_b: EventSequence<E>,
     event_count: usize,
+    max_len: Option<usize>,
 }
 
 impl<E: Event> Default for Events<E> {
@@ -171,6 +186,7 @@ impl<E: Event> Default for Events<E> {
             events_a: EventSequence::default(),
             events_b: EventSequence::default(),
             event_count: 0,
+            max_len: None,
         }
     }
 }
@@ -188,8 +204,50 @@ impl<E: Event> Events<E> {
         let id = EventId::new(self.event_count);
         let instance = EventInstance { id, event };
 
-        self.events_b.push(instance);
+        self.events_b.push_back(instance);
         self.event_count += 1;
+
+        self.evict();
+    }
+
+    /// Limits the number of retained events to `max_len`, evicting the oldest events once more
+    /// are sent, or removes the limit if `max_len` is `None`.
+    ///
+    /// Readers that fall behind miss the evicted events, see
+    /// [`ManualEventReader::missed_events`].
+    #[inline]
+    pub fn set_max_len(&mut self, max_len: Option<usize>) {
+        self.max_len = max_len;
+        self.evict();
+    }
+
+    #[inline]
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Removes the oldest events until there are at most `self.max_len`.
+    ///
+    /// The events are stored in ring buffers, so this only costs the number of evicted events.
+    #[inline]
+    fn evict(&mut self) {
+        let excess = match self.max_len {
+            Some(max_len) => self.len().saturating_sub(max_len),
+            None => return,
+        };
+
+        let evict_a = usize::min(excess, self.events_a.len());
+        self.events_a.drain(..evict_a);
+        self.events_a.start_event_count += evict_a;
+
+        let evict_b = excess - evict_a;
+        if evict_b > 0 {
+            self.events_b.drain(..evict_b);
+            self.events_b.start_event_count += evict_b;
+
+            // `events_a` is empty, keep it starting where `events_b` does
+            self.events_a.start_event_count = self.events_b.start_event_count;
+        }
     }
 
     #[inline]
@@ -236,6 +294,34 @@ impl<E: Event> Events<E> {
             .chain(self.events_b.drain(..))
             .map(|e| e.event)
     }
+
+    /// Drains all events into `buf`, oldest first, without allocating a new buffer.
+    ///
+    /// `buf` is appended to, not cleared, so it can be reused between frames.
+    #[inline]
+    pub fn drain_into(&mut self, buf: &mut Vec<E>) {
+        buf.reserve(self.len());
+        buf.extend(self.drain());
+    }
+
+    /// Takes all events out of `self`, returning the older and newer events respectively.
+    ///
+    /// The events are drained, so the internal buffers keep their capacity for later events.
+    /// Readers will not see any of the taken events.
+    #[inline]
+    pub fn take(
+        &mut self,
+    ) -> (
+        impl DoubleEndedIterator<Item = E> + '_,
+        impl DoubleEndedIterator<Item = E> + '_,
+    ) {
+        self.reset();
+
+        let older = self.events_a.drain(..).map(|e| e.event);
+        let newer = self.events_b.drain(..).map(|e| e.event);
+
+        (older, newer)
+    }
 }
 
 /// A reader for [`Event`]s.
@@ -272,6 +358,33 @@ impl<'w, 's, E: Event> EventReader<'w, 's, E> {
     }
 }
 
+/// An [`Event`] targeting a specific [`Entity`].
+pub trait TargetedEvent: Event {
+    fn target(&self) -> Entity;
+}
+
+impl<'w, 's, E: TargetedEvent> EventReader<'w, 's, E> {
+    /// Iterates over the events whose target matches `query`.
+    ///
+    /// Events that don't match are still marked as read.
+    #[inline]
+    pub fn iter_for<'a, Q: WorldQuery, F: ReadOnlyWorldQuery>(
+        &'a mut self,
+        query: &'a Query<'a, 'a, Q, F>,
+    ) -> impl DoubleEndedIterator<Item = &'a E> {
+        self.iter()
+            .filter(move |event| query.contains(event.target()))
+    }
+
+    /// Iterates over the events targeting `entity`.
+    ///
+    /// Events that don't match are still marked as read.
+    #[inline]
+    pub fn iter_targeting(&mut self, entity: Entity) -> impl DoubleEndedIterator<Item = &E> {
+        self.iter().filter(move |event| event.target() == entity)
+    }
+}
+
 #[doc(hidden)]
 #[derive(Debug)]
 pub struct EventReaderState<E: Event> {
@@ -396,6 +509,18 @@ impl<E: Event> Default for ManualEventReader<E> {
 }
 
 impl<E: Event> ManualEventReader<E> {
+    /// Forks `self`, returning an independent reader at the same position.
+    ///
+    /// Both readers will see the same unread and future events, reading from one doesn't
+    /// affect the other.
+    #[inline]
+    pub fn clone_cursor(&self) -> Self {
+        Self {
+            last_event_count: self.last_event_count,
+            _marker: PhantomData,
+        }
+    }
+
     #[inline]
     pub fn iter<'a>(&'a mut self, events: &'a Events<E>) -> impl DoubleEndedIterator<Item = &'a E> {
         self.iter_with_id(events).map(|(_, event)| event)
@@ -406,16 +531,13 @@ impl<E: Event> ManualEventReader<E> {
         &'a mut self,
         events: &'a Events<E>,
     ) -> impl DoubleEndedIterator<Item = (EventId<E>, &'a E)> {
-        let a_index = (self.last_event_count).saturating_sub(events.events_a.start_event_count);
-        let b_index = (self.last_event_count).saturating_sub(events.events_b.start_event_count);
-        let a = events.events_a.get(a_index..).unwrap_or_default();
-        let b = events.events_b.get(b_index..).unwrap_or_default();
+        let (a, b) = self.unread(events);
 
         let unread_count = a.len() + b.len();
 
         self.last_event_count = events.event_count - unread_count;
 
-        let iterator = a.iter().chain(b.iter());
+        let iterator = a.chain(b);
         iterator
             .map(move |e| (e.id, &e.event))
             .inspect(move |(id, _)| {
@@ -423,6 +545,21 @@ impl<E: Event> ManualEventReader<E> {
             })
     }
 
+    /// Returns the unread events in the older and newer buffer of `events`.
+    #[inline]
+    fn unread<'a>(
+        &self,
+        events: &'a Events<E>,
+    ) -> (
+        vec_deque::Iter<'a, EventInstance<E>>,
+        vec_deque::Iter<'a, EventInstance<E>>,
+    ) {
+        let a = events.events_a.since(self.last_event_count);
+        let b = events.events_b.since(self.last_event_count);
+
+        (a, b)
+    }
+
     #[inline]
     pub fn missed_events(&self, events: &Events<E>) -> usize {
         events
@@ -443,3 +580,433 @@ impl<E: Event> ManualEventReader<E> {
         self.len(events) == 0
     }
 }
+
+/// A run criteria that runs only when there are unread events of type `E`.
+///
+/// The criteria has its own [`ManualEventReader`], so it doesn't consume the events of the
+/// [`EventReader`]s in the systems it runs.
+///
+/// ```
+/// # use shiv::prelude::*;
+/// # use shiv::event::on_event;
+/// struct Jump;
+///
+/// fn jump(mut events: EventReader<Jump>) {}
+///
+/// let mut stage = SystemStage::sequential().with_run_criteria(on_event::<Jump>);
+/// stage.add_system(jump);
+/// ```
+#[inline]
+pub fn on_event<E: Event>(
+    mut reader: Local<ManualEventReader<E>>,
+    events: Option<Res<Events<E>>>,
+) -> ShouldRun {
+    match events {
+        Some(events) => (reader.iter(&events).count() > 0).into(),
+        None => ShouldRun::No,
+    }
+}
+
+/// The reader used by [`World::read_events`].
+struct WorldEventReader<E: Event> {
+    reader: ManualEventReader<E>,
+}
+
+impl<E: Event> Default for WorldEventReader<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            reader: ManualEventReader::default(),
+        }
+    }
+}
+
+impl World {
+    /// Sends `event`, initializing [`Events<E>`] if it doesn't exist.
+    #[inline]
+    pub fn send_event<E: Event>(&mut self, event: E) {
+        self.resource_or_default::<Events<E>>().send(event);
+    }
+
+    /// Reads all events of type `E` not yet read through this method, oldest first.
+    ///
+    /// The world keeps its own [`ManualEventReader`], so this doesn't affect any
+    /// [`EventReader`]s.
+    #[inline]
+    pub fn read_events<E: Event>(&mut self) -> impl DoubleEndedIterator<Item = &E> {
+        self.init_resource::<Events<E>>();
+
+        let event_count = self.resource::<Events<E>>().event_count;
+        let mut world_reader = self.resource_or_default::<WorldEventReader<E>>();
+        let reader = world_reader.reader.clone_cursor();
+        world_reader.reader.last_event_count = event_count;
+
+        let (a, b) = reader.unread(self.resource::<Events<E>>());
+        a.chain(b).map(|e| &e.event)
+    }
+}
+
+/// A resource storing observer systems, keyed by the [`TypeId`] of the event they observe.
+///
+/// Observers are added with [`World::add_observer`] and run by [`World::trigger`].
+#[derive(Default)]
+pub struct Observers {
+    observers: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Observers {
+    #[inline]
+    pub fn contains<E: Event>(&self) -> bool {
+        self.observers.contains_key(&TypeId::of::<E>())
+    }
+
+    #[inline]
+    fn get_or_default<E: Event>(&mut self) -> &mut Vec<BoxedSystem<E, ()>> {
+        let observers = (self.observers.entry(TypeId::of::<E>()))
+            .or_insert_with(|| Box::new(Vec::<BoxedSystem<E, ()>>::new()));
+
+        observers.downcast_mut().unwrap()
+    }
+
+    #[inline]
+    fn take<E: Event>(&mut self) -> Option<Vec<BoxedSystem<E, ()>>> {
+        let observers = self.observers.remove(&TypeId::of::<E>())?;
+        Some(*observers.downcast().unwrap())
+    }
+}
+
+/// Triggered by [`World::despawn`] right before `entity` is despawned, observers can still
+/// read its components.
+///
+/// ```
+/// # use shiv::prelude::*;
+/// # use shiv::{event::OnDespawn, system::In};
+/// # #[derive(Component)]
+/// # struct Loot(u32);
+/// fn drop_loot(despawned: In<OnDespawn>, loot: Query<&Loot>, mut dropped: ResMut<Vec<u32>>) {
+///     if let Some(loot) = loot.get(despawned.entity) {
+///         dropped.push(loot.0);
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Vec<u32>>();
+/// world.add_observer(drop_loot);
+///
+/// let entity = world.spawn().insert(Loot(3)).entity();
+/// world.despawn(entity);
+/// assert_eq!(*world.resource::<Vec<u32>>(), vec![3]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OnDespawn {
+    pub entity: Entity,
+}
+
+impl TargetedEvent for OnDespawn {
+    #[inline]
+    fn target(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl std::fmt::Debug for Observers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Observers")
+            .field("events", &self.observers.len())
+            .finish()
+    }
+}
+
+impl World {
+    /// Adds an `observer` system, that is run immediately every time an event of type `E` is
+    /// [`trigger`](World::trigger)ed. The event is passed to the observer as
+    /// [`In<E>`](crate::system::In).
+    #[inline]
+    pub fn add_observer<E: Event + Clone, Params>(
+        &mut self,
+        observer: impl IntoSystem<E, (), Params>,
+    ) {
+        let mut system = observer.into_system();
+        system.init(self);
+
+        let mut observers = self.resource_or_default::<Observers>();
+        observers.get_or_default::<E>().push(Box::new(system));
+    }
+
+    /// Runs all observers of `E` immediately, applying their buffers after each observer.
+    ///
+    /// Triggering `E` from within an observer of `E` does nothing.
+    #[inline]
+    pub fn trigger<E: Event + Clone>(&mut self, event: E) {
+        let systems = match self.get_resource_mut::<Observers>() {
+            Some(mut observers) => observers.take::<E>(),
+            None => None,
+        };
+
+        let mut systems = match systems {
+            Some(systems) => systems,
+            None => return,
+        };
+
+        for system in systems.iter_mut() {
+            system.run(event.clone(), self);
+            system.apply(self);
+        }
+
+        // observers added while triggering are kept after the existing ones
+        let mut observers = self.resource_or_default::<Observers>();
+        let added = observers.get_or_default::<E>();
+        systems.append(added);
+        *added = systems;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        query::{Query, With},
+        system::{Commands, In, ResMut, SystemState},
+        world::{Entities, Entity, World},
+    };
+
+    use super::{EventReader, Events, ManualEventReader, OnDespawn, TargetedEvent};
+
+    #[derive(Clone)]
+    struct Damage(u32);
+
+    struct Health(u32);
+
+    #[test]
+    fn observer() {
+        fn apply_damage(damage: In<Damage>, mut health: ResMut<Health>) {
+            health.0 = health.0.saturating_sub(damage.0);
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Health(10));
+        world.add_observer(apply_damage);
+
+        world.trigger(Damage(3));
+        assert_eq!(world.resource::<Health>().0, 7);
+
+        world.trigger(Damage(4));
+        assert_eq!(world.resource::<Health>().0, 3);
+
+        world.trigger(0u32);
+        assert_eq!(world.resource::<Health>().0, 3);
+    }
+
+    #[test]
+    fn clone_cursor() {
+        let mut events = Events::<u32>::default();
+        let mut reader = ManualEventReader::default();
+
+        events.send(0);
+        assert_eq!(reader.iter(&events).copied().collect::<Vec<_>>(), vec![0]);
+
+        events.send(1);
+        let mut fork = reader.clone_cursor();
+        events.send(2);
+
+        assert_eq!(
+            reader.iter(&events).copied().collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(reader.is_empty(&events));
+
+        events.send(3);
+        assert_eq!(
+            fork.iter(&events).copied().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(reader.iter(&events).copied().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn max_len() {
+        let mut events = Events::<u32>::default();
+        let mut reader = ManualEventReader::default();
+        events.set_max_len(Some(3));
+
+        events.send(0);
+        events.send(1);
+        events.update();
+        events.send(2);
+        events.send(3);
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(reader.missed_events(&events), 1);
+        assert_eq!(
+            reader.iter(&events).copied().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        for event in 4..8 {
+            events.send(event);
+        }
+
+        assert_eq!(reader.missed_events(&events), 1);
+        assert_eq!(
+            reader.iter(&events).copied().collect::<Vec<_>>(),
+            vec![5, 6, 7]
+        );
+
+        events.set_max_len(None);
+        events.send(8);
+        assert_eq!(events.len(), 4);
+    }
+
+    #[test]
+    fn drain_into() {
+        let mut events = Events::<u32>::default();
+        let mut reader = ManualEventReader::default();
+        let mut buf = vec![0];
+
+        events.send(1);
+        events.update();
+        events.send(2);
+
+        events.drain_into(&mut buf);
+        assert_eq!(buf, vec![0, 1, 2]);
+        assert!(events.is_empty());
+        assert!(reader.is_empty(&events));
+
+        events.send(3);
+        events.update();
+        events.send(4);
+
+        let (older, newer) = events.take();
+        assert_eq!(older.collect::<Vec<_>>(), vec![3]);
+        assert_eq!(newer.collect::<Vec<_>>(), vec![4]);
+        assert!(events.is_empty());
+        assert!(reader.is_empty(&events));
+
+        events.send(5);
+        assert_eq!(reader.iter(&events).copied().collect::<Vec<_>>(), vec![5]);
+    }
+
+    #[test]
+    fn world_events() {
+        let mut world = World::new();
+        assert_eq!(world.read_events::<u32>().count(), 0);
+
+        world.send_event(1u32);
+        world.send_event(2u32);
+        assert_eq!(
+            world.read_events::<u32>().copied().collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(world.read_events::<u32>().count(), 0);
+
+        world.resource_mut::<Events<u32>>().update();
+        world.send_event(3u32);
+        assert_eq!(
+            world.read_events::<u32>().copied().collect::<Vec<_>>(),
+            vec![3]
+        );
+
+        let mut reader = ManualEventReader::<u32>::default();
+        let events = world.resource::<Events<u32>>();
+        assert_eq!(
+            reader.iter(events).copied().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn despawn_observer() {
+        fn record(
+            despawned: In<OnDespawn>,
+            entities: &Entities,
+            query: Query<&i32>,
+            mut records: ResMut<Vec<(bool, Option<i32>)>>,
+        ) {
+            let alive = entities.contains(despawned.entity);
+            records.push((alive, query.get(despawned.entity).copied()));
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Vec<(bool, Option<i32>)>>();
+
+        let a = world.spawn().insert(1i32).entity();
+        let b = world.spawn().entity();
+        world.despawn(a);
+
+        world.add_observer(record);
+
+        let c = world.spawn().insert(2i32).entity();
+        assert!(world.despawn(c));
+        assert!(!world.contains_entity(c));
+        assert!(!world.despawn(c));
+
+        world.with_commands(|commands| commands.entity(b).despawn());
+
+        let records = world.resource::<Vec<(bool, Option<i32>)>>();
+        assert_eq!(*records, vec![(true, Some(2)), (true, None)]);
+    }
+
+    #[test]
+    fn despawn_observer_respawn() {
+        fn respawn(despawned: In<OnDespawn>, mut commands: Commands) {
+            let entity = despawned.entity;
+            commands.add_fn(move |world| {
+                world.despawn(entity);
+                world.spawn().insert(3i32);
+            });
+        }
+
+        let mut world = World::new();
+        world.add_observer(respawn);
+
+        let entity = world.spawn().insert(1i32).entity();
+        assert!(world.despawn(entity));
+        assert!(!world.contains_entity(entity));
+
+        // the respawned entity reuses the index, and must keep its components
+        let respawned = world.entities().get(entity.index() as usize).unwrap();
+        assert_ne!(respawned, entity);
+        assert_eq!(world.get::<i32>(respawned), Some(&3));
+    }
+
+    #[test]
+    fn targeted_events() {
+        struct Hit {
+            target: Entity,
+            damage: u32,
+        }
+
+        impl TargetedEvent for Hit {
+            fn target(&self) -> Entity {
+                self.target
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Events<Hit>>();
+
+        let a = world.spawn().insert(true).entity();
+        let b = world.spawn().entity();
+        let c = world.spawn().insert(true).entity();
+
+        let mut events = world.resource_mut::<Events<Hit>>();
+        for (i, target) in [a, b, c, b, a].into_iter().enumerate() {
+            events.send(Hit {
+                target,
+                damage: i as u32,
+            });
+        }
+
+        let mut state =
+            SystemState::<(EventReader<Hit>, Query<Entity, With<bool>>)>::new(&mut world);
+
+        let (mut reader, query) = state.get_mut(&mut world);
+        let damage = reader.iter_for(&query).map(|hit| hit.damage);
+        assert_eq!(damage.collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert!(reader.is_empty());
+
+        let mut state = SystemState::<EventReader<Hit>>::new(&mut world);
+        let mut reader = state.get(&world);
+        let damage = reader.iter_targeting(b).map(|hit| hit.damage);
+        assert_eq!(damage.collect::<Vec<_>>(), vec![1, 3]);
+    }
+}
@@ -1,8 +1,11 @@
 use std::{mem, ops::Deref};
 
 use crate::{
+    hash_map::HashSet,
     prelude::{Command, Commands, EntityCommands},
+    query::{Query, Without},
     storage::DenseStorage,
+    system::CommandError,
     world::{Component, Entity, EntityMut, World},
 };
 
@@ -36,12 +39,19 @@ pub struct Children {
 }
 
 impl Children {
+    /// Returns the child at `index`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Entity> {
+        self.entities.get(index).copied()
+    }
+
+    /// Removes `entity`, preserving the order of the remaining children.
     #[inline]
     pub fn remove(&mut self, entity: Entity) -> bool {
         let index = self.entities.iter().position(|e| *e == entity);
 
         if let Some(index) = index {
-            self.entities.swap_remove(index);
+            self.entities.remove(index);
 
             true
         } else {
@@ -63,6 +73,58 @@ impl Deref for Children {
     }
 }
 
+/// Iterates over every entity in `query`, that is, every entity without a [`Parent`].
+#[inline]
+pub fn iter_roots<'a>(
+    query: &'a Query<'_, '_, Entity, Without<Parent>>,
+) -> impl Iterator<Item = Entity> + 'a {
+    query.iter()
+}
+
+/// A depth-first iterator over a hierarchy, yielding each entity together with its depth
+/// relative to the start entity.
+///
+/// Children are visited in the order they appear in [`Children`]. Entities that have already
+/// been visited are skipped, so a corrupt hierarchy containing cycles will not loop forever.
+pub struct HierarchyDfs<'a, 'w, 's> {
+    children: &'a Query<'w, 's, &'static Children>,
+    stack: Vec<(Entity, usize)>,
+    visited: HashSet<Entity>,
+}
+
+impl<'a, 'w, 's> HierarchyDfs<'a, 'w, 's> {
+    #[inline]
+    pub fn new(children: &'a Query<'w, 's, &'static Children>, start: Entity) -> Self {
+        Self {
+            children,
+            stack: vec![(start, 0)],
+            visited: HashSet::default(),
+        }
+    }
+}
+
+impl<'a, 'w, 's> Iterator for HierarchyDfs<'a, 'w, 's> {
+    type Item = (Entity, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (entity, depth) = self.stack.pop()?;
+
+            if !self.visited.insert(entity) {
+                continue;
+            }
+
+            if let Some(children) = self.children.get(entity) {
+                let children = children.iter().rev();
+                self.stack.extend(children.map(|&child| (child, depth + 1)));
+            }
+
+            return Some((entity, depth));
+        }
+    }
+}
+
 impl World {
     #[inline]
     pub fn set_parent(&mut self, child: Entity, new_parent: Entity) {
@@ -94,6 +156,29 @@ impl World {
         }
     }
 
+    /// Sets the parent of `child` to `parent`, placing it at `index` in the [`Children`] of
+    /// `parent`.
+    ///
+    /// `index` is clamped to the number of children, if `child` already is a child of `parent`
+    /// it's moved to `index`.
+    #[inline]
+    pub fn add_child_at(&mut self, parent: Entity, child: Entity, index: usize) {
+        if !self.contains_entity(child) || !self.contains_entity(parent) {
+            return;
+        }
+
+        self.set_parent(child, parent);
+
+        let mut children = self
+            .get_mut::<Children>(parent)
+            .expect("hierarchy is corrupt");
+
+        children.remove(child);
+
+        let index = usize::min(index, children.len());
+        children.entities.insert(index, child);
+    }
+
     #[inline]
     pub fn remove_parent(&mut self, child: Entity) {
         if let Some(parent) = self.remove::<Parent>(child) {
@@ -143,6 +228,27 @@ impl World {
         }
     }
 
+    /// Removes `entity` from its parent's [`Children`] and orphans its children, leaving
+    /// `entity` outside of any hierarchy.
+    #[inline]
+    pub(crate) fn detach_hierarchy(&mut self, entity: Entity) {
+        if let Some(parent) = self.remove::<Parent>(entity) {
+            if let Some(mut children) = self.get_mut::<Children>(parent.entity) {
+                children.remove(entity);
+            }
+        }
+
+        if let Some(children) = self.remove::<Children>(entity) {
+            for child in children.entities {
+                let parent = self.get::<Parent>(child).map(Parent::entity);
+
+                if parent == Some(entity) {
+                    self.remove::<Parent>(child);
+                }
+            }
+        }
+    }
+
     #[inline]
     fn despawn_recursive_internal(&mut self, entity: Entity) {
         if let Some(mut children) = self.get_mut::<Children>(entity) {
@@ -201,6 +307,11 @@ impl<'w> EntityMut<'w> {
         self.world.set_parent(child, self.entity);
     }
 
+    #[inline]
+    pub fn add_child_at(&mut self, child: Entity, index: usize) {
+        self.world.add_child_at(self.entity, child, index);
+    }
+
     #[inline]
     pub fn remove_parent(&mut self) {
         self.world.remove_parent(self.entity);
@@ -233,8 +344,24 @@ pub struct SetParent {
 }
 
 impl Command for SetParent {
-    fn apply(self: Box<Self>, world: &mut World) {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
         world.set_parent(self.child, self.new_parent);
+
+        Ok(())
+    }
+}
+
+pub struct AddChildAt {
+    pub parent: Entity,
+    pub child: Entity,
+    pub index: usize,
+}
+
+impl Command for AddChildAt {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        world.add_child_at(self.parent, self.child, self.index);
+
+        Ok(())
     }
 }
 
@@ -243,8 +370,10 @@ pub struct RemoveParent {
 }
 
 impl Command for RemoveParent {
-    fn apply(self: Box<Self>, world: &mut World) {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
         world.remove_parent(self.child);
+
+        Ok(())
     }
 }
 
@@ -254,8 +383,10 @@ pub struct RemoveChild {
 }
 
 impl Command for RemoveChild {
-    fn apply(self: Box<Self>, world: &mut World) {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
         world.remove_child(self.parent, self.child);
+
+        Ok(())
     }
 }
 
@@ -264,8 +395,22 @@ pub struct RemoveChildren {
 }
 
 impl Command for RemoveChildren {
-    fn apply(self: Box<Self>, world: &mut World) {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
         world.remove_children(self.parent);
+
+        Ok(())
+    }
+}
+
+pub struct DespawnDescendants {
+    pub entity: Entity,
+}
+
+impl Command for DespawnDescendants {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        world.despawn_children(self.entity);
+
+        Ok(())
     }
 }
 
@@ -327,6 +472,15 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         })
     }
 
+    #[inline]
+    pub fn add_child_at(&mut self, child: Entity, index: usize) -> &mut Self {
+        self.add_command(AddChildAt {
+            parent: self.entity,
+            child,
+            index,
+        })
+    }
+
     #[inline]
     pub fn remove_parent(&mut self) -> &mut Self {
         self.add_command(RemoveParent { child: self.entity })
@@ -346,4 +500,164 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
             parent: self.entity,
         })
     }
+
+    /// Despawns all descendants of this entity, keeping the entity itself.
+    #[inline]
+    pub fn despawn_descendants(&mut self) -> &mut Self {
+        self.add_command(DespawnDescendants {
+            entity: self.entity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::{CommandQueue, SystemState};
+
+    #[test]
+    fn hierarchy_dfs() {
+        let mut world = World::new();
+
+        let root = world.spawn().entity();
+        let a = world.spawn().entity();
+        let b = world.spawn().entity();
+        let a_a = world.spawn().entity();
+        let a_b = world.spawn().entity();
+        let b_a = world.spawn().entity();
+        let other = world.spawn().entity();
+
+        world.set_parent(a, root);
+        world.set_parent(b, root);
+        world.set_parent(a_a, a);
+        world.set_parent(a_b, a);
+        world.set_parent(b_a, b);
+
+        let mut state =
+            SystemState::<(Query<Entity, Without<Parent>>, Query<&Children>)>::new(&mut world);
+        let (roots, children) = state.get_mut(&mut world);
+
+        let roots = iter_roots(&roots).collect::<Vec<_>>();
+        assert_eq!(roots, vec![root, other]);
+
+        let order = HierarchyDfs::new(&children, root).collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![(root, 0), (a, 1), (a_a, 2), (a_b, 2), (b, 1), (b_a, 2)]
+        );
+    }
+
+    #[test]
+    fn hierarchy_dfs_cycle() {
+        let mut world = World::new();
+
+        let a = world.spawn().entity();
+        let b = world.spawn().entity();
+
+        world.set_parent(b, a);
+        world.entity_mut(b).insert(Children { entities: vec![a] });
+
+        let mut state = SystemState::<Query<&Children>>::new(&mut world);
+        let children = state.get(&world);
+
+        let order = HierarchyDfs::new(&children, a).collect::<Vec<_>>();
+        assert_eq!(order, vec![(a, 0), (b, 1)]);
+    }
+
+    #[test]
+    fn despawn_descendants() {
+        let mut world = World::new();
+
+        let parent = world.spawn().entity();
+        let child = world.spawn().entity();
+        let grandchild = world.spawn().entity();
+
+        world.set_parent(child, parent);
+        world.set_parent(grandchild, child);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(parent).despawn_descendants();
+        queue.apply(&mut world);
+
+        assert!(world.contains_entity(parent));
+        assert!(!world.contains_entity(child));
+        assert!(!world.contains_entity(grandchild));
+        assert!(world.get::<Children>(parent).unwrap().is_empty());
+    }
+
+    #[test]
+    fn ordered_children() {
+        const WIDTH: f32 = 10.0;
+
+        fn layout(world: &World, parent: Entity) -> Vec<(Entity, f32)> {
+            let children = world.get::<Children>(parent).unwrap();
+            let positions = children.iter().enumerate();
+            positions.map(|(i, &e)| (e, i as f32 * WIDTH)).collect()
+        }
+
+        let mut world = World::new();
+
+        let row = world.spawn().entity();
+        let a = world.spawn().entity();
+        let b = world.spawn().entity();
+        let c = world.spawn().entity();
+        let d = world.spawn().entity();
+        let e = world.spawn().entity();
+
+        world.set_parent(a, row);
+        world.set_parent(b, row);
+        world.set_parent(c, row);
+        world.add_child_at(row, d, 1);
+
+        assert_eq!(&**world.get::<Children>(row).unwrap(), &[a, d, b, c]);
+        assert_eq!(world.get::<Parent>(d).unwrap().entity(), row);
+
+        world.remove_child(row, b);
+        assert_eq!(
+            layout(&world, row),
+            vec![(a, 0.0), (d, WIDTH), (c, 2.0 * WIDTH)]
+        );
+
+        world.entity_mut(row).add_child_at(c, 0);
+        assert_eq!(&**world.get::<Children>(row).unwrap(), &[c, a, d]);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(row).add_child_at(e, usize::MAX);
+        queue.apply(&mut world);
+
+        let children = world.get::<Children>(row).unwrap();
+        assert_eq!(&**children, &[c, a, d, e]);
+        assert_eq!(children.get(1), Some(a));
+        assert_eq!(children.get(4), None);
+    }
+
+    #[test]
+    fn despawn_detaches_hierarchy() {
+        let mut world = World::new();
+
+        let root = world.spawn().entity();
+        let node = world.spawn().entity();
+        let sibling = world.spawn().entity();
+        let leaf = world.spawn().entity();
+
+        world.set_parent(node, root);
+        world.set_parent(sibling, root);
+        world.set_parent(leaf, node);
+
+        assert!(world.despawn(node));
+
+        assert_eq!(&**world.get::<Children>(root).unwrap(), &[sibling]);
+        assert!(world.contains_entity(leaf));
+        assert!(world.get::<Parent>(leaf).is_none());
+
+        world.set_parent(leaf, root);
+        world.remove_parent(sibling);
+        world.despawn_recursive(root);
+
+        assert!(!world.contains_entity(root));
+        assert!(!world.contains_entity(leaf));
+        assert!(world.contains_entity(sibling));
+    }
 }
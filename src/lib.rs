@@ -32,7 +32,7 @@ pub mod prelude {
     pub use crate::schedule::{
         DefaultStage, IntoSystemDescriptor, Schedule, Stage, StageLabel, SystemLabel, SystemStage,
     };
-    pub use crate::storage::{DenseStorage, Resource};
+    pub use crate::storage::{DenseStorage, Resource, SparseStorage};
     pub use crate::system::{
         Command, Commands, EntityCommands, IntoPipeSystem, Local, Res, ResInit, ResMut, ResMutInit,
         SystemParam,
@@ -43,16 +43,19 @@ pub mod prelude {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::sync::atomic::Ordering;
 
     use crate as shiv;
     use crate::query::Or;
     use crate::{
         query::{Added, Changed, Query, With},
         schedule::{IntoSystemDescriptor, Schedule, ShouldRun, StageLabel, SystemStage},
-        system::{Commands, Local, ResMut},
-        world::{Entity, World},
+        system::{Commands, Local, ResMut, SystemState},
+        world::{Component, Entity, World},
     };
 
+    use super::tasks;
+
     #[derive(StageLabel)]
     enum TestStage {
         A,
@@ -218,4 +221,161 @@ mod tests {
         schedule.run_once(&mut world);
         schedule.run_once(&mut world);
     }
+
+    #[test]
+    fn query_get_component() {
+        let mut world = World::new();
+        let a = world.spawn().insert(1i32).insert(true).entity();
+        let b = world.spawn().insert(2i32).entity();
+
+        let mut state = SystemState::<Query<(&i32, &mut bool)>>::new(&mut world);
+        let query = state.get_mut(&mut world);
+
+        assert_eq!(query.get_component::<i32>(a), Some(&1));
+        assert_eq!(query.get_component::<bool>(a), Some(&true));
+        assert_eq!(query.get_component::<i32>(b), None);
+        assert_eq!(query.get_component::<f32>(a), None);
+    }
+
+    #[test]
+    fn change_detection_wraparound() {
+        use crate::change_detection::CHECK_TICK_THRESHOLD;
+        use crate::system::Res;
+
+        #[derive(Default)]
+        struct Detected {
+            changed: usize,
+            added: usize,
+            resource_changed: bool,
+        }
+
+        fn detect_system(
+            changed: Query<&i32, Changed<i32>>,
+            added: Query<&i32, Added<i32>>,
+            resource: Res<f32>,
+            mut detected: ResMut<Detected>,
+        ) {
+            detected.changed = changed.iter().count();
+            detected.added = added.iter().count();
+            detected.resource_changed = resource.is_changed();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Detected>();
+        world.insert_resource(0.0f32);
+        let entity = world.spawn().insert(0i32).entity();
+
+        let mut schedule = Schedule::new().with_stage(TestStage::A, SystemStage::sequential());
+        schedule.add_system_to_stage(TestStage::A, detect_system);
+
+        schedule.run_once(&mut world);
+        let detected = world.resource::<Detected>();
+        assert_eq!((detected.changed, detected.added), (1, 1));
+        assert!(detected.resource_changed);
+
+        // advance the change tick past the wrap boundary, checking ticks as a schedule would
+        for _ in 0..u32::MAX / CHECK_TICK_THRESHOLD + 1 {
+            world
+                .change_tick
+                .fetch_add(CHECK_TICK_THRESHOLD, Ordering::AcqRel);
+            schedule.run_once(&mut world);
+
+            let detected = world.resource::<Detected>();
+            assert_eq!((detected.changed, detected.added), (0, 0));
+            assert!(!detected.resource_changed);
+        }
+
+        *world.get_mut::<i32>(entity).unwrap() += 1;
+        *world.resource_mut::<f32>() += 1.0;
+        schedule.run_once(&mut world);
+
+        let detected = world.resource::<Detected>();
+        assert_eq!((detected.changed, detected.added), (1, 0));
+        assert!(detected.resource_changed);
+
+        schedule.run_once(&mut world);
+
+        let detected = world.resource::<Detected>();
+        assert_eq!((detected.changed, detected.added), (0, 0));
+        assert!(!detected.resource_changed);
+    }
+
+    #[test]
+    fn query_get_change_ticks() {
+        let mut world = World::new();
+        let entity = world.spawn().insert(0i32).entity();
+        let recorded = world.increment_change_tick();
+
+        let mut schedule = Schedule::new().with_stage(TestStage::A, SystemStage::sequential());
+        schedule.add_system_to_stage(TestStage::A, increment_system);
+        schedule.run_once(&mut world);
+
+        let mut state = SystemState::<Query<&i32>>::new(&mut world);
+        let query = state.get(&world);
+
+        let ticks = query.get_change_ticks::<i32>(entity).unwrap();
+        assert!(ticks.is_changed(recorded, world.change_tick()));
+        assert!(!ticks.is_added(recorded, world.change_tick()));
+        assert!(query.get_change_ticks::<bool>(entity).is_none());
+    }
+
+    #[test]
+    fn query_read_and_changed_filter() {
+        fn changed_system(query: Query<(Entity, &i32), Changed<i32>>, mut out: ResMut<Vec<i32>>) {
+            out.extend(query.iter().map(|(_, &i)| i));
+        }
+
+        fn changed_mut_system(mut query: Query<(Entity, &mut i32), Changed<i32>>) {
+            for (_, mut i) in query.iter_mut() {
+                *i += 10;
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Vec<i32>>();
+        world.spawn().insert(1);
+
+        let mut schedule = default_schedule();
+        schedule.add_system_to_stage(TestStage::A, changed_mut_system);
+        schedule.add_system_to_stage(TestStage::B, changed_system);
+        schedule.run_once(&mut world);
+
+        assert_eq!(*world.resource::<Vec<i32>>(), vec![11]);
+    }
+
+    #[test]
+    fn par_iter_mut() {
+        #[derive(Component)]
+        struct Position(f32);
+
+        #[derive(Component)]
+        struct Velocity(f32);
+
+        let mut world = World::new();
+        for i in 0..1000 {
+            world
+                .spawn()
+                .insert(Position(i as f32))
+                .insert(Velocity(2.0));
+        }
+        world.spawn().insert(Position(-1.0));
+
+        let task_pool = tasks::TaskPool::global();
+        world.par_iter_mut::<(&mut Position, &Velocity)>(task_pool, 64, |(mut p, v)| {
+            p.0 += v.0;
+        });
+
+        let query = world.query::<(&Position, Option<&Velocity>)>();
+        for (position, velocity) in query.iter(&world) {
+            if velocity.is_some() {
+                assert!(position.0 >= 2.0);
+                assert_eq!(position.0.fract(), 0.0);
+            } else {
+                assert_eq!(position.0, -1.0);
+            }
+        }
+
+        let sum: f32 = query.iter(&world).map(|(p, _)| p.0).sum();
+        assert_eq!(sum, (0..1000).sum::<i32>() as f32 + 2000.0 - 1.0);
+    }
 }
@@ -0,0 +1,210 @@
+use crate::{
+    storage::ComponentStorage,
+    system::FilteredAccess,
+    world::{ComponentId, Entities, Entity, EntityIdSet, World, WorldId},
+};
+
+use super::{get_entities, matches};
+
+/// A query built at runtime from [`ComponentId`]s rather than types.
+///
+/// Components added with [`DynamicQuery::read`] are fetched as raw pointers, in the order
+/// they were added.
+///
+/// # Example
+/// ```rust
+/// # use shiv::{prelude::*, query::DynamicQuery};
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// # #[derive(Component)]
+/// # struct Dead;
+/// let mut world = World::new();
+/// world.spawn().insert(Health(10));
+///
+/// let health = world.init_component::<Health>();
+/// let dead = world.init_component::<Dead>();
+///
+/// let query = DynamicQuery::new(&world).read(health).without(dead);
+/// for item in query.iter(&world) {
+///     let health = unsafe { &*(item.get(0) as *const Health) };
+///     assert_eq!(health.0, 10);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DynamicQuery {
+    world_id: WorldId,
+    reads: Vec<ComponentId>,
+    filtered_access: FilteredAccess<ComponentId>,
+}
+
+impl DynamicQuery {
+    #[inline]
+    pub fn new(world: &World) -> Self {
+        let mut filtered_access = FilteredAccess::new();
+        filtered_access.grow(world.components.len());
+
+        Self {
+            world_id: world.id(),
+            reads: Vec::new(),
+            filtered_access,
+        }
+    }
+
+    /// Adds a read of the component `id`, only entities with `id` will be matched.
+    #[inline]
+    pub fn read(mut self, id: ComponentId) -> Self {
+        self.reads.push(id);
+        self.filtered_access.add_read(id);
+        self
+    }
+
+    /// Only match entities with the component `id`.
+    #[inline]
+    pub fn with(mut self, id: ComponentId) -> Self {
+        self.filtered_access.add_with(id);
+        self
+    }
+
+    /// Only match entities without the component `id`.
+    #[inline]
+    pub fn without(mut self, id: ComponentId) -> Self {
+        self.filtered_access.add_without(id);
+        self
+    }
+
+    #[inline]
+    pub fn reads(&self) -> &[ComponentId] {
+        &self.reads
+    }
+
+    #[inline]
+    pub fn access(&self) -> &FilteredAccess<ComponentId> {
+        &self.filtered_access
+    }
+
+    #[inline]
+    pub fn validate_world(&self, world: &World) {
+        if self.world_id != world.id() {
+            panic!("DynamicQuery used with a different world");
+        }
+    }
+
+    #[inline]
+    pub fn get_entities(&self, world: &World) -> EntityIdSet {
+        self.validate_world(world);
+        get_entities(&self.filtered_access, world)
+    }
+
+    #[inline]
+    pub fn matches(&self, world: &World, entity: Entity) -> bool {
+        self.validate_world(world);
+        matches(&self.filtered_access, world, entity)
+    }
+
+    #[inline]
+    pub fn get<'w>(&self, world: &'w World, entity: Entity) -> Option<DynamicItem<'w>> {
+        if !world.contains_entity(entity) || !self.matches(world, entity) {
+            return None;
+        }
+
+        let storages = self.storages(world)?;
+        Some(unsafe { DynamicItem::fetch(&storages, entity) })
+    }
+
+    #[inline]
+    pub fn iter<'w>(&self, world: &'w World) -> DynamicQueryIter<'w> {
+        let (entity_ids, storages) = match self.storages(world) {
+            Some(storages) => (self.get_entities(world), storages),
+            None => (EntityIdSet::new(), Vec::new()),
+        };
+
+        DynamicQueryIter {
+            entity_ids,
+            current_index: 0,
+            entities: &world.entities,
+            storages,
+        }
+    }
+
+    /// Returns the storage of every read component, or `None` if any of them was never
+    /// initialized, in which case no entity can match.
+    #[inline]
+    fn storages<'w>(&self, world: &'w World) -> Option<Vec<&'w dyn ComponentStorage>> {
+        let storages = self.reads.iter();
+        storages.map(|&id| world.storage.get(id)).collect()
+    }
+}
+
+/// A single item fetched by a [`DynamicQuery`].
+#[derive(Clone, Debug)]
+pub struct DynamicItem<'w> {
+    entity: Entity,
+    components: Vec<*const u8>,
+    marker: std::marker::PhantomData<&'w World>,
+}
+
+impl<'w> DynamicItem<'w> {
+    /// # Safety
+    /// - `entity` must be contained in every storage in `storages`.
+    #[inline]
+    unsafe fn fetch(storages: &[&'w dyn ComponentStorage], entity: Entity) -> Self {
+        let components = storages.iter();
+
+        Self {
+            entity,
+            components: components
+                .map(|storage| unsafe { storage.get_unchecked(entity) as *const u8 })
+                .collect(),
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns a pointer to the `index`th read component, valid for `'w`.
+    ///
+    /// # Panics
+    /// - If `index` is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> *const u8 {
+        self.components[index]
+    }
+
+    #[inline]
+    pub fn components(&self) -> &[*const u8] {
+        &self.components
+    }
+}
+
+/// An iterator over the items of a [`DynamicQuery`], in the same order as
+/// [`QueryIter`](super::QueryIter).
+pub struct DynamicQueryIter<'w> {
+    entity_ids: EntityIdSet,
+    current_index: usize,
+    entities: &'w Entities,
+    storages: Vec<&'w dyn ComponentStorage>,
+}
+
+impl<'w> Iterator for DynamicQueryIter<'w> {
+    type Item = DynamicItem<'w>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current_index < self.entity_ids.len() {
+            let index = self.current_index;
+            self.current_index += 1;
+
+            if self.entity_ids.contains(index) {
+                let entity = unsafe { self.entities.get_unchecked(index) };
+
+                // SAFETY: every read component is in `with`, so `entity` is in every storage.
+                return Some(unsafe { DynamicItem::fetch(&self.storages, entity) });
+            }
+        }
+
+        None
+    }
+}
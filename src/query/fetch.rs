@@ -7,6 +7,9 @@ use crate::{
     world::{Component, ComponentId, Entity, Storage, World},
 };
 
+/// # Safety
+/// - [`WorldQuery::update_component_access`] must add every component accessed by
+///   [`WorldQuery::fetch`] and [`WorldQuery::filter_fetch`].
 pub unsafe trait WorldQuery {
     type Item<'w>;
     type Fetch<'w>;
@@ -51,6 +54,8 @@ pub unsafe trait WorldQuery {
     fn matches_component_set(state: &Self::State, id: ComponentId) -> bool;
 }
 
+/// # Safety
+/// - Implementors must only access components immutably.
 pub unsafe trait ReadOnlyWorldQuery: WorldQuery<ReadOnly = Self> {}
 
 pub type QueryItem<'w, Q> = <Q as WorldQuery>::Item<'w>;
@@ -1,4 +1,4 @@
-use std::{any::type_name, marker::PhantomData};
+use std::marker::PhantomData;
 
 use crate::{
     storage::ComponentStorage,
@@ -128,6 +128,10 @@ unsafe impl<T: Component> WorldQuery for Without<T> {
 
 unsafe impl<T: Component> ReadOnlyWorldQuery for Without<T> {}
 
+/// A filter matching entities that match any of the filters in the tuple `T`.
+///
+/// `Or<()>` matches no entities, as no filter in it matches, and `Or<(A,)>` matches the same
+/// entities as `A`.
 pub struct Or<T>(T);
 
 #[doc(hidden)]
@@ -157,9 +161,11 @@ macro_rules! impl_or_world_query {
                 },)*) }
             }
 
+            // `fetch` checks `contains` of each filter itself, so any one containing `entity`
+            // is enough
             #[inline]
             fn contains<'w>(($($ident,)*): &mut Self::Fetch<'w>, entity: Entity) -> bool {
-                $($ident::contains(&mut $ident.fetch, entity) &&)* true
+                $($ident::contains(&mut $ident.fetch, entity) ||)* false
             }
 
             #[inline]
@@ -249,7 +255,7 @@ unsafe impl<T: Component> WorldQuery for Added<T> {
         change_tick: u32,
     ) -> Self::Fetch<'w> {
         let storage_sets = <T::Storage as Storage>::get(&world.storage);
-        let storage = unsafe { storage_sets.get_unchecked(state) };
+        let storage = storage_sets.get(state).unwrap();
 
         AddedFetch {
             storage,
@@ -287,12 +293,8 @@ unsafe impl<T: Component> WorldQuery for Added<T> {
 
     #[inline]
     fn update_component_access(&state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
-        assert!(
-            !access.has_write(state),
-            "&{} conflicts with previous access in this query. Shared access cannot coexist with exclusive access.", 
-            type_name::<T>(),
-        );
-
+        // only the change ticks are read, which doesn't conflict with the query itself
+        // writing `T`, e.g. `Query<&mut T, Changed<T>>`
         access.add_read(state);
     }
 
@@ -331,7 +333,7 @@ unsafe impl<T: Component> WorldQuery for Changed<T> {
         change_tick: u32,
     ) -> Self::Fetch<'w> {
         let storage_sets = <T::Storage as Storage>::get(&world.storage);
-        let storage = unsafe { storage_sets.get_unchecked(state) };
+        let storage = storage_sets.get(state).unwrap();
 
         ChangedFetch {
             storage,
@@ -369,12 +371,8 @@ unsafe impl<T: Component> WorldQuery for Changed<T> {
 
     #[inline]
     fn update_component_access(&state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
-        assert!(
-            !access.has_write(state),
-            "&{} conflicts with previous access in this query. Shared access cannot coexist with exclusive access.", 
-            type_name::<T>(),
-        );
-
+        // only the change ticks are read, which doesn't conflict with the query itself
+        // writing `T`, e.g. `Query<&mut T, Changed<T>>`
         access.add_read(state);
     }
 
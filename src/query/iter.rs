@@ -2,6 +2,11 @@ use crate::world::{Entities, EntityIdSet, World};
 
 use super::{QueryItem, QueryState, ReadOnlyWorldQuery, WorldQuery};
 
+/// An iterator over the items of a [`QueryState`].
+///
+/// Items are yielded in ascending order of [`Entity::index`](crate::world::Entity::index),
+/// regardless of the order their components were inserted or removed in. Indices of despawned
+/// entities are reused, so this isn't necessarily the order the entities were spawned in.
 #[allow(dead_code)]
 pub struct QueryIter<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
     pub(crate) query_state: &'s QueryState<Q, F>,
@@ -9,6 +14,10 @@ pub struct QueryIter<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
 }
 
 impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryIter<'w, 's, Q, F> {
+    /// # Safety
+    /// - `query_state` must match `world`.
+    /// - This function does not check borrow rules, so it's up to the caller to ensure that access
+    ///   is valid.
     #[inline]
     pub unsafe fn new(
         query_state: &'s QueryState<Q, F>,
@@ -1,8 +1,10 @@
+mod dynamic;
 mod fetch;
 mod filter;
 mod iter;
 mod query;
 
+pub use dynamic::*;
 pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
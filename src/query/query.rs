@@ -1,10 +1,61 @@
+use hyena::TaskPool;
+
 use crate::{
+    change_detection::ChangeTicks,
+    hash_map::HashMap,
     system::FilteredAccess,
-    world::{ComponentId, Entity, EntityIdSet, World, WorldId},
+    world::{Component, ComponentId, Entity, EntityIdSet, World, WorldId},
 };
 
 use super::{QueryItem, QueryIter, ReadOnlyQueryItem, ReadOnlyWorldQuery, WorldQuery};
 
+/// The error returned by [`Query::get_single`] when the query doesn't match exactly one entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuerySingleError {
+    NoEntities,
+    MultipleEntities,
+}
+
+impl std::fmt::Display for QuerySingleError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoEntities => write!(f, "query didn't match any entities"),
+            Self::MultipleEntities => write!(f, "query matched more than one entity"),
+        }
+    }
+}
+
+impl std::error::Error for QuerySingleError {}
+
+/// The error returned by [`Query::get_many`] and [`Query::get_many_mut`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryEntityError {
+    /// The entity doesn't exist.
+    NoSuchEntity(Entity),
+    /// The entity exists but doesn't match the query.
+    QueryDoesNotMatch(Entity),
+    /// The entity was requested more than once with mutable access.
+    AliasedMutability(Entity),
+}
+
+impl std::fmt::Display for QueryEntityError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSuchEntity(entity) => write!(f, "entity {} does not exist", entity),
+            Self::QueryDoesNotMatch(entity) => {
+                write!(f, "entity {} does not match the query", entity)
+            }
+            Self::AliasedMutability(entity) => {
+                write!(f, "entity {} was requested mutably more than once", entity)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryEntityError {}
+
 #[derive(Debug)]
 pub struct QueryState<Q: WorldQuery, F: ReadOnlyWorldQuery> {
     pub(crate) world_id: WorldId,
@@ -20,6 +71,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         let filter_state = F::init_state(world);
 
         let mut filtered_access = FilteredAccess::new();
+        filtered_access.grow(world.components.len());
         Q::update_component_access(&query_state, &mut filtered_access);
         F::update_component_access(&filter_state, &mut filtered_access);
 
@@ -31,44 +83,27 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         }
     }
 
+    /// Retargets `self` to `world`, so it can be used with a different world than it was
+    /// created with.
+    ///
+    /// Component ids are assigned per world, so the state is initialized again for `world`.
     #[inline]
-    pub fn get_entities(&self, world: &World) -> EntityIdSet {
-        self.debug_validate_world(world);
-
-        let mut iter = self.filtered_access.iter_with();
-        let mut entities = if let Some(id) = iter.next() {
-            world.storage.entity_ids(id)
-        } else {
-            world.entities.entity_ids().clone()
-        };
-        for id in iter {
-            entities.intersect_with(&world.storage.entity_ids(id));
-        }
-
-        for id in self.filtered_access.iter_without() {
-            entities.difference_with(&world.storage.entity_ids(id));
+    pub fn update_for_world(&mut self, world: &mut World) {
+        if self.world_id != world.id() {
+            *self = Self::new(world);
         }
+    }
 
-        entities
+    #[inline]
+    pub fn get_entities(&self, world: &World) -> EntityIdSet {
+        self.debug_validate_world(world);
+        get_entities(&self.filtered_access, world)
     }
 
     #[inline]
     pub fn matches(&self, world: &World, entity: Entity) -> bool {
         self.debug_validate_world(world);
-
-        for id in self.filtered_access.iter_with() {
-            if !world.storage.contains(id, entity) {
-                return false;
-            }
-        }
-
-        for id in self.filtered_access.iter_without() {
-            if world.storage.contains(id, entity) {
-                return false;
-            }
-        }
-
-        true
+        matches(&self.filtered_access, world, entity)
     }
 
     #[inline]
@@ -90,6 +125,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
 
     /// # Safety
     /// - `world` must be the same world that was used to create this [`QueryState`].
+    /// - This doesn't check borrow rules, so it's up to the caller to ensure that access is valid.
     #[inline]
     pub unsafe fn get_unchecked_manual<'w>(
         &self,
@@ -119,6 +155,56 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     }
 }
 
+/// Returns the ids of every entity matching the `with` and `without` filters of `access`.
+#[inline]
+pub(crate) fn get_entities(access: &FilteredAccess<ComponentId>, world: &World) -> EntityIdSet {
+    let mut iter = access.iter_with();
+    let mut entities = if let Some(id) = iter.next() {
+        world.storage.entity_ids(id)
+    } else {
+        world.entities.entity_ids().clone()
+    };
+    for id in iter {
+        entities.intersect_with(&world.storage.entity_ids(id));
+    }
+
+    for id in access.iter_without() {
+        entities.difference_with(&world.storage.entity_ids(id));
+    }
+
+    entities
+}
+
+/// Returns [`QueryEntityError::AliasedMutability`] if any entity in `entities` is repeated.
+#[inline]
+fn verify_distinct(entities: &[Entity]) -> Result<(), QueryEntityError> {
+    for (i, &entity) in entities.iter().enumerate() {
+        if entities[..i].contains(&entity) {
+            return Err(QueryEntityError::AliasedMutability(entity));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `entity` matches the `with` and `without` filters of `access`.
+#[inline]
+pub(crate) fn matches(access: &FilteredAccess<ComponentId>, world: &World, entity: Entity) -> bool {
+    for id in access.iter_with() {
+        if !world.storage.contains(id, entity) {
+            return false;
+        }
+    }
+
+    for id in access.iter_without() {
+        if world.storage.contains(id, entity) {
+            return false;
+        }
+    }
+
+    true
+}
+
 impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     #[inline]
     pub fn validate_world(&self, world: &World) {
@@ -167,8 +253,81 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         }
     }
 
+    /// Gets the items of every entity in `entities`.
+    ///
+    /// # Safety
+    /// - `world` must be the same world that was used to create this [`QueryState`].
+    /// - This doesn't check borrow rules, so it's up to the caller to ensure that access is valid.
+    /// - `entities` must be distinct unless `Q` is read-only.
+    #[inline]
+    pub unsafe fn get_many_unchecked_manual<'w, const N: usize>(
+        &self,
+        world: &'w World,
+        entities: [Entity; N],
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Result<[QueryItem<'w, Q>; N], QueryEntityError> {
+        for entity in entities {
+            if !world.contains_entity(entity) {
+                return Err(QueryEntityError::NoSuchEntity(entity));
+            }
+        }
+
+        let items = entities.map(|entity| unsafe {
+            self.get_unchecked_manual(world, entity, last_change_tick, change_tick)
+        });
+
+        if let Some(index) = items.iter().position(Option::is_none) {
+            return Err(QueryEntityError::QueryDoesNotMatch(entities[index]));
+        }
+
+        Ok(items.map(Option::unwrap))
+    }
+
+    #[inline]
+    pub fn get_many<'w, const N: usize>(
+        &self,
+        world: &'w World,
+        entities: [Entity; N],
+    ) -> Result<[ReadOnlyQueryItem<'w, Q>; N], QueryEntityError> {
+        self.validate_world(world);
+
+        let state = self.as_readonly();
+        unsafe {
+            state.get_many_unchecked_manual(
+                world,
+                entities,
+                world.last_change_tick(),
+                world.change_tick(),
+            )
+        }
+    }
+
+    /// Gets the items of every entity in `entities` mutably at once.
+    ///
+    /// Returns [`QueryEntityError::AliasedMutability`] if any entity appears more than once.
+    #[inline]
+    pub fn get_many_mut<'w, const N: usize>(
+        &mut self,
+        world: &'w mut World,
+        entities: [Entity; N],
+    ) -> Result<[QueryItem<'w, Q>; N], QueryEntityError> {
+        self.validate_world(world);
+        verify_distinct(&entities)?;
+
+        unsafe {
+            self.get_many_unchecked_manual(
+                world,
+                entities,
+                world.last_change_tick(),
+                world.change_tick(),
+            )
+        }
+    }
+
     /// # Safety
     /// - `world` must be the same world that was used to create this [`QueryState`].
+    /// - This doesn't check borrow rules, so it's up to the caller to ensure that access is valid.
     #[inline]
     pub unsafe fn iter_unchecked_manual<'w, 's>(
         &'s self,
@@ -180,6 +339,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         unsafe { QueryIter::new(self, world, last_change_tick, change_tick) }
     }
 
+    /// Iterates the query in ascending order of entity index, see [`QueryIter`].
     #[inline]
     pub fn iter<'w, 's>(&'s self, world: &'w World) -> QueryIter<'w, 's, Q::ReadOnly, F::ReadOnly> {
         self.validate_world(world);
@@ -197,9 +357,293 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         self.validate_world(world);
         unsafe { self.iter_unchecked_manual(world, world.last_change_tick(), world.change_tick()) }
     }
+
+    /// Gets the only item of the query, or an error if it matches no or multiple entities.
+    ///
+    /// # Safety
+    /// - `world` must be the same world that was used to create this [`QueryState`].
+    /// - This doesn't check borrow rules, so it's up to the caller to ensure that access is valid.
+    #[inline]
+    pub unsafe fn get_single_unchecked_manual<'w>(
+        &self,
+        world: &'w World,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Result<QueryItem<'w, Q>, QuerySingleError> {
+        let mut iter = unsafe { self.iter_unchecked_manual(world, last_change_tick, change_tick) };
+
+        match (iter.next(), iter.next()) {
+            (Some(item), None) => Ok(item),
+            (None, _) => Err(QuerySingleError::NoEntities),
+            (Some(_), Some(_)) => Err(QuerySingleError::MultipleEntities),
+        }
+    }
+
+    #[inline]
+    pub fn get_single<'w>(
+        &self,
+        world: &'w World,
+    ) -> Result<ReadOnlyQueryItem<'w, Q>, QuerySingleError> {
+        self.validate_world(world);
+
+        let state = self.as_readonly();
+        unsafe {
+            state.get_single_unchecked_manual(world, world.last_change_tick(), world.change_tick())
+        }
+    }
+
+    #[inline]
+    pub fn get_single_mut<'w>(
+        &mut self,
+        world: &'w mut World,
+    ) -> Result<QueryItem<'w, Q>, QuerySingleError> {
+        self.validate_world(world);
+
+        unsafe {
+            self.get_single_unchecked_manual(world, world.last_change_tick(), world.change_tick())
+        }
+    }
+
+    /// Gets the only item of the query.
+    ///
+    /// # Panics
+    /// - If the query doesn't match exactly one entity, see [`QueryState::get_single`].
+    #[inline]
+    #[track_caller]
+    pub fn single<'w>(&self, world: &'w World) -> ReadOnlyQueryItem<'w, Q> {
+        match self.get_single(world) {
+            Ok(item) => item,
+            Err(err) => panic!("{}: {}", err, std::any::type_name::<Self>()),
+        }
+    }
+
+    /// Gets the only item of the query.
+    ///
+    /// # Panics
+    /// - If the query doesn't match exactly one entity, see [`QueryState::get_single_mut`].
+    #[inline]
+    #[track_caller]
+    pub fn single_mut<'w>(&mut self, world: &'w mut World) -> QueryItem<'w, Q> {
+        match self.get_single_mut(world) {
+            Ok(item) => item,
+            Err(err) => panic!("{}: {}", err, std::any::type_name::<Self>()),
+        }
+    }
+
+    /// Runs `func` on every item of the query, together with the [`Entity`] it belongs to.
+    ///
+    /// This walks the matched entities directly, so `Entity` doesn't need to be part of `Q`.
+    /// Entities are visited in the same order as [`QueryState::iter`].
+    ///
+    /// # Safety
+    /// - `world` must be the same world that was used to create this [`QueryState`].
+    /// - This doesn't check borrow rules, so it's up to the caller to ensure that access is valid.
+    #[inline]
+    pub unsafe fn for_each_entity_unchecked_manual<'w>(
+        &self,
+        world: &'w World,
+        func: impl FnMut(Entity, QueryItem<'w, Q>),
+        last_change_tick: u32,
+        change_tick: u32,
+    ) {
+        self.debug_validate_world(world);
+
+        let entities = self.get_entities(world);
+        unsafe {
+            self.for_each_in_unchecked_manual(world, &entities, func, last_change_tick, change_tick)
+        }
+    }
+
+    /// # Safety
+    /// - See [`QueryState::for_each_entity_unchecked_manual`].
+    /// - `entities` must only contain entities matching the query access.
+    #[inline]
+    unsafe fn for_each_in_unchecked_manual<'w>(
+        &self,
+        world: &'w World,
+        entities: &EntityIdSet,
+        mut func: impl FnMut(Entity, QueryItem<'w, Q>),
+        last_change_tick: u32,
+        change_tick: u32,
+    ) {
+        let mut fetch =
+            unsafe { Q::init_fetch(world, &self.query_state, last_change_tick, change_tick) };
+        let mut filter =
+            unsafe { F::init_fetch(world, &self.filter_state, last_change_tick, change_tick) };
+
+        for index in entities.iter() {
+            let entity = unsafe { world.entities.get_unchecked(index) };
+
+            if unsafe { F::filter_fetch(&mut filter, entity) } {
+                func(entity, unsafe { Q::fetch(&mut fetch, entity) });
+            }
+        }
+    }
+
+    #[inline]
+    pub fn for_each_entity<'w>(
+        &self,
+        world: &'w World,
+        func: impl FnMut(Entity, ReadOnlyQueryItem<'w, Q>),
+    ) {
+        self.validate_world(world);
+        unsafe {
+            self.as_readonly().for_each_entity_unchecked_manual(
+                world,
+                func,
+                world.last_change_tick(),
+                world.change_tick(),
+            )
+        }
+    }
+
+    #[inline]
+    pub fn for_each_entity_mut<'w>(
+        &mut self,
+        world: &'w mut World,
+        func: impl FnMut(Entity, QueryItem<'w, Q>),
+    ) {
+        self.validate_world(world);
+
+        let last_change_tick = world.last_change_tick();
+        let change_tick = world.change_tick();
+
+        unsafe { self.for_each_entity_unchecked_manual(world, func, last_change_tick, change_tick) }
+    }
+
+    /// Collects the query into a map from [`Entity`] to the result of `func` on each item.
+    ///
+    /// The map is sized from the matched entities up front, so it never has to grow.
+    ///
+    /// # Safety
+    /// - `world` must be the same world that was used to create this [`QueryState`].
+    /// - This doesn't check borrow rules, so it's up to the caller to ensure that access is valid.
+    #[inline]
+    pub unsafe fn to_entity_map_unchecked_manual<'w, V>(
+        &self,
+        world: &'w World,
+        mut func: impl FnMut(QueryItem<'w, Q>) -> V,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> HashMap<Entity, V> {
+        self.debug_validate_world(world);
+
+        let entities = self.get_entities(world);
+        let mut map = HashMap::with_capacity_and_hasher(entities.count(), Default::default());
+
+        let insert = |entity: Entity, item: QueryItem<'w, Q>| {
+            map.insert(entity, func(item));
+        };
+
+        unsafe {
+            self.for_each_in_unchecked_manual(
+                world,
+                &entities,
+                insert,
+                last_change_tick,
+                change_tick,
+            )
+        };
+
+        map
+    }
+
+    /// Collects the query into a map from [`Entity`] to the result of `func` on each item.
+    #[inline]
+    pub fn to_entity_map<'w, V>(
+        &self,
+        world: &'w World,
+        func: impl FnMut(ReadOnlyQueryItem<'w, Q>) -> V,
+    ) -> HashMap<Entity, V> {
+        self.validate_world(world);
+        unsafe {
+            self.as_readonly().to_entity_map_unchecked_manual(
+                world,
+                func,
+                world.last_change_tick(),
+                world.change_tick(),
+            )
+        }
+    }
+
+    /// Runs `func` on every item of the query in parallel on `task_pool`.
+    ///
+    /// The matched entities are split into batches of `batch_size` entities, each batch is run
+    /// as a single task. Batches are disjoint, so no two tasks ever fetch the same entity.
+    /// Unlike [`QueryState::iter`], items aren't visited in any particular order.
+    ///
+    /// # Safety
+    /// - `world` must be the same world that was used to create this [`QueryState`].
+    /// - This doesn't check borrow rules, so it's up to the caller to ensure that access is valid.
+    #[inline]
+    pub unsafe fn par_for_each_unchecked_manual<'w>(
+        &self,
+        world: &'w World,
+        task_pool: &TaskPool,
+        batch_size: usize,
+        func: impl Fn(QueryItem<'w, Q>) + Send + Sync,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) {
+        self.debug_validate_world(world);
+
+        let entity_ids = self.get_entities(world).iter().collect::<Vec<_>>();
+        let func = &func;
+
+        task_pool.scope(|scope| {
+            for batch in entity_ids.chunks(batch_size.max(1)) {
+                scope.spawn(async move {
+                    let mut fetch = unsafe {
+                        Q::init_fetch(world, &self.query_state, last_change_tick, change_tick)
+                    };
+                    let mut filter = unsafe {
+                        F::init_fetch(world, &self.filter_state, last_change_tick, change_tick)
+                    };
+
+                    for &index in batch {
+                        let entity = unsafe { world.entities.get_unchecked(index) };
+
+                        if unsafe { F::filter_fetch(&mut filter, entity) } {
+                            func(unsafe { Q::fetch(&mut fetch, entity) });
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    /// Runs `func` on every item of the query in parallel on `task_pool`, see
+    /// [`QueryState::par_for_each_unchecked_manual`].
+    #[inline]
+    pub fn par_for_each_mut<'w>(
+        &mut self,
+        world: &'w mut World,
+        task_pool: &TaskPool,
+        batch_size: usize,
+        func: impl Fn(QueryItem<'w, Q>) + Send + Sync,
+    ) {
+        self.validate_world(world);
+
+        let last_change_tick = world.last_change_tick();
+        let change_tick = world.change_tick();
+
+        unsafe {
+            self.par_for_each_unchecked_manual(
+                world,
+                task_pool,
+                batch_size,
+                func,
+                last_change_tick,
+                change_tick,
+            )
+        };
+    }
 }
 
-pub struct Query<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
+// the `'static` bounds are implied wherever a `Query` is named, so that queries nested in
+// other parameters, e.g. `ParamSet<(Query<&mut T>, Query<&T>)>`, can be used as system
+// parameters without spelling out `'static`
+pub struct Query<'w, 's, Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static = ()> {
     world: &'w World,
     state: &'s QueryState<Q, F>,
     last_change_tick: u32,
@@ -242,6 +686,71 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Gets the component `C` of `entity`.
+    ///
+    /// Returns `None` if `C` isn't read by the query or if `entity` doesn't match the query.
+    #[inline]
+    pub fn get_component<C: Component>(&self, entity: Entity) -> Option<&C> {
+        let id = self.world.components.get_component::<C>()?;
+
+        if !self.state.filtered_access.has_read(id) || !self.contains(entity) {
+            return None;
+        }
+
+        self.world.get::<C>(entity)
+    }
+
+    /// Gets the items of every entity in `entities`.
+    #[inline]
+    pub fn get_many<const N: usize>(
+        &self,
+        entities: [Entity; N],
+    ) -> Result<[ReadOnlyQueryItem<'_, Q>; N], QueryEntityError> {
+        unsafe {
+            self.state.as_readonly().get_many_unchecked_manual(
+                self.world,
+                entities,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Gets the items of every entity in `entities` mutably at once.
+    ///
+    /// Returns [`QueryEntityError::AliasedMutability`] if any entity appears more than once.
+    #[inline]
+    pub fn get_many_mut<const N: usize>(
+        &mut self,
+        entities: [Entity; N],
+    ) -> Result<[QueryItem<'_, Q>; N], QueryEntityError> {
+        verify_distinct(&entities)?;
+
+        unsafe {
+            self.state.get_many_unchecked_manual(
+                self.world,
+                entities,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Gets the [`ChangeTicks`] of component `C` on `entity`, which can be compared against
+    /// arbitrary ticks with [`ChangeTicks::is_changed`] and [`ChangeTicks::is_added`].
+    ///
+    /// Returns `None` if `C` isn't read by the query or if `entity` doesn't match the query.
+    #[inline]
+    pub fn get_change_ticks<C: Component>(&self, entity: Entity) -> Option<ChangeTicks> {
+        let id = self.world.components.get_component::<C>()?;
+
+        if !self.state.filtered_access.has_read(id) || !self.contains(entity) {
+            return None;
+        }
+
+        self.world.get_change_ticks::<C>(entity)
+    }
+
     #[inline]
     pub fn get_mut(&mut self, entity: Entity) -> Option<QueryItem<'_, Q>> {
         unsafe {
@@ -254,6 +763,7 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Iterates the query in ascending order of entity index, see [`QueryIter`].
     #[inline]
     pub fn iter(&self) -> QueryIter<'_, 's, Q::ReadOnly, F::ReadOnly> {
         unsafe {
@@ -265,11 +775,105 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Runs `func` on every item of the query, together with the [`Entity`] it belongs to.
+    #[inline]
+    pub fn for_each_entity<'a>(&'a self, func: impl FnMut(Entity, ReadOnlyQueryItem<'a, Q>)) {
+        unsafe {
+            self.state.as_readonly().for_each_entity_unchecked_manual(
+                self.world,
+                func,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Runs `func` on every item of the query, together with the [`Entity`] it belongs to.
+    #[inline]
+    pub fn for_each_entity_mut<'a>(&'a mut self, func: impl FnMut(Entity, QueryItem<'a, Q>)) {
+        unsafe {
+            self.state.for_each_entity_unchecked_manual(
+                self.world,
+                func,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Collects the query into a map from [`Entity`] to the result of `func` on each item.
+    ///
+    /// The map is sized from the matched entities up front, so it never has to grow.
+    #[inline]
+    pub fn to_entity_map<'a, V>(
+        &'a self,
+        func: impl FnMut(ReadOnlyQueryItem<'a, Q>) -> V,
+    ) -> HashMap<Entity, V> {
+        unsafe {
+            self.state.as_readonly().to_entity_map_unchecked_manual(
+                self.world,
+                func,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
     #[inline]
     pub fn iter_mut(&mut self) -> QueryIter<'_, 's, Q, F> {
         let state = &self.state;
         unsafe { state.iter_unchecked_manual(self.world, self.last_change_tick, self.change_tick) }
     }
+
+    /// Gets the only item of the query, or an error if it matches no or multiple entities.
+    #[inline]
+    pub fn get_single(&self) -> Result<ReadOnlyQueryItem<'_, Q>, QuerySingleError> {
+        unsafe {
+            self.state.as_readonly().get_single_unchecked_manual(
+                self.world,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Gets the only item of the query, or an error if it matches no or multiple entities.
+    #[inline]
+    pub fn get_single_mut(&mut self) -> Result<QueryItem<'_, Q>, QuerySingleError> {
+        unsafe {
+            self.state.get_single_unchecked_manual(
+                self.world,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Gets the only item of the query.
+    ///
+    /// # Panics
+    /// - If the query doesn't match exactly one entity, see [`Query::get_single`].
+    #[inline]
+    #[track_caller]
+    pub fn single(&self) -> ReadOnlyQueryItem<'_, Q> {
+        match self.get_single() {
+            Ok(item) => item,
+            Err(err) => panic!("{}: {}", err, std::any::type_name::<Self>()),
+        }
+    }
+
+    /// Gets the only item of the query.
+    ///
+    /// # Panics
+    /// - If the query doesn't match exactly one entity, see [`Query::get_single_mut`].
+    #[inline]
+    #[track_caller]
+    pub fn single_mut(&mut self) -> QueryItem<'_, Q> {
+        match self.get_single_mut() {
+            Ok(item) => item,
+            Err(err) => panic!("{}: {}", err, std::any::type_name::<Self>()),
+        }
+    }
 }
 
 impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> IntoIterator for &'w Query<'_, 's, Q, F> {
@@ -8,6 +8,17 @@ pub trait SystemExecutor: Downcast + Send + Sync {
     #[inline]
     fn systems_changed(&mut self, _systems: &[SystemContainer]) {}
 
+    /// Creates an empty executor of the same kind as `self`.
+    ///
+    /// Stages interleaving exclusive and parallel systems keep an executor per batch of
+    /// parallel systems, so [`SystemExecutor::systems_changed`] is only called when the systems
+    /// change. If this returns `None`, `self` is notified of each batch before running it
+    /// instead.
+    #[inline]
+    fn fork(&self) -> Option<Box<dyn SystemExecutor>> {
+        None
+    }
+
     /// # Safety
     /// - `world` must be the same world that each `system` was initialized with.
     unsafe fn run_systems(&mut self, systems: &mut [SystemContainer], world: &mut World);
@@ -31,6 +42,11 @@ impl_downcast!(SystemExecutor);
 pub struct SequentialExecutor;
 
 impl SystemExecutor for SequentialExecutor {
+    #[inline]
+    fn fork(&self) -> Option<Box<dyn SystemExecutor>> {
+        Some(Box::new(SequentialExecutor))
+    }
+
     #[inline]
     unsafe fn run_systems(&mut self, systems: &mut [SystemContainer], world: &mut World) {
         for system in systems {
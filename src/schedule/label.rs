@@ -76,11 +76,15 @@ define_label!(
     /// A label that can be used to identify a schedule stage.
     StageLabel,
     /// A unique identifier for a schedule stage.
+    ///
+    /// This is itself a [`StageLabel`], so it can be stored to use a label chosen at runtime.
     StageLabelId,
 );
 define_label!(
     /// A label that can be used to identify a system.
     SystemLabel,
     /// A unique identifier for a system.
+    ///
+    /// This is itself a [`SystemLabel`], so it can be stored to use a label chosen at runtime.
     SystemLabelId
 );
@@ -7,7 +7,7 @@ use hyena::{Scope, TaskPool};
 use tracing::Instrument;
 
 use crate::{
-    system::FilteredAccess,
+    system::FilteredAccessSet,
     world::{ComponentId, World},
 };
 
@@ -19,7 +19,7 @@ struct ParallelSystemMeta {
     dependants: Vec<usize>,
     dependencies_total: usize,
     dependencies_remaining: usize,
-    access: FilteredAccess<ComponentId>,
+    access: FilteredAccessSet<ComponentId>,
 }
 
 #[derive(Debug)]
@@ -29,7 +29,8 @@ pub struct ParallelExecutor {
     finished_receiver: Receiver<usize>,
     queued: FixedBitSet,
     running: FixedBitSet,
-    current_access: FilteredAccess<ComponentId>,
+    skipped: FixedBitSet,
+    current_access: FilteredAccessSet<ComponentId>,
     task_pool: TaskPool,
 }
 
@@ -56,7 +57,8 @@ impl ParallelExecutor {
             finished_receiver,
             queued: FixedBitSet::new(),
             running: FixedBitSet::new(),
-            current_access: FilteredAccess::default(),
+            skipped: FixedBitSet::new(),
+            current_access: FilteredAccessSet::default(),
             task_pool,
         }
     }
@@ -78,8 +80,15 @@ impl ParallelExecutor {
         systems: &'a mut [SystemContainer],
         world: &'a World,
     ) {
+        self.skipped.clear();
+
         for (index, (meta, system)) in self.system_meta.iter_mut().zip(systems).enumerate() {
+            if meta.dependencies_total > 0 {
+                meta.dependencies_remaining = meta.dependencies_total;
+            }
+
             if !system.should_run() {
+                self.skipped.insert(index);
                 continue;
             }
 
@@ -87,10 +96,6 @@ impl ParallelExecutor {
             let access_compatible = meta.access.is_compatible(&self.current_access);
             let can_run = dependencies_run && access_compatible;
 
-            if meta.dependencies_total > 0 {
-                meta.dependencies_remaining = meta.dependencies_total;
-            }
-
             if dependencies_run && !access_compatible {
                 self.queued.set(index, true);
             }
@@ -135,6 +140,13 @@ impl ParallelExecutor {
                 scope.spawn(task);
             }
         }
+
+        // skipped systems finish immediately, once all their dependencies have
+        for index in self.skipped.clone().ones() {
+            if self.system_meta[index].dependencies_total == 0 {
+                self.process_finished_system(index);
+            }
+        }
     }
 
     #[inline]
@@ -147,7 +159,11 @@ impl ParallelExecutor {
             dependant_meta.dependencies_remaining -= 1;
 
             if dependant_meta.dependencies_remaining == 0 {
-                self.queued.insert(dependant);
+                if self.skipped.contains(dependant) {
+                    self.process_finished_system(dependant);
+                } else {
+                    self.queued.insert(dependant);
+                }
             }
         }
     }
@@ -183,6 +199,7 @@ impl SystemExecutor for ParallelExecutor {
 
         self.queued.grow(systems.len());
         self.running.grow(systems.len());
+        self.skipped.grow(systems.len());
 
         for container in systems {
             let dependencies_total = container.dependencies().len();
@@ -205,6 +222,12 @@ impl SystemExecutor for ParallelExecutor {
         }
     }
 
+    #[inline]
+    fn fork(&self) -> Option<Box<dyn SystemExecutor>> {
+        let executor = Self::new_with_task_pool(self.task_pool.clone());
+        Some(Box::new(executor))
+    }
+
     unsafe fn run_systems(&mut self, systems: &mut [SystemContainer], world: &mut World) {
         self.task_pool.clone().scope(|scope| {
             let executor = async {
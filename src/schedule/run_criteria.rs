@@ -41,6 +41,11 @@ impl Into<bool> for ShouldRun {
     }
 }
 
+/// Decides whether a stage or system should run.
+///
+/// The criteria is initialized again whenever it's run with a different world, just like the
+/// systems of a [`SystemStage`](super::SystemStage), so stateful criteria such as
+/// [`ShouldRun::once`] start over for each world.
 #[derive(Debug, Default)]
 pub struct RunCriteria {
     criteria: Option<BoxedSystem<(), ShouldRun>>,
@@ -65,10 +70,10 @@ impl RunCriteria {
                 }
             }
 
-            // SAFETY:
-            // - world has been validated above
-            // - world is borrowed mutably
-            unsafe { criteria.run_unchecked((), world) }
+            let should_run = criteria.run((), world);
+            criteria.apply(world);
+
+            should_run
         } else {
             ShouldRun::Yes
         }
@@ -116,3 +121,95 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        event::{on_event, EventReader, Events},
+        schedule::{Stage, SystemStage},
+        system::{Local, ResMut},
+        world::World,
+    };
+
+    use super::ShouldRun;
+
+    struct Trigger;
+
+    fn count_system(mut counter: ResMut<u32>) {
+        *counter += 1;
+    }
+
+    #[test]
+    fn event_reader_criteria() {
+        fn on_trigger(mut reader: EventReader<Trigger>) -> ShouldRun {
+            (reader.iter().count() > 0).into()
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.init_resource::<Events<Trigger>>();
+
+        let mut stage = SystemStage::sequential().with_run_criteria(on_trigger);
+        stage.add_system(count_system);
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 0);
+
+        world.resource_mut::<Events<Trigger>>().send(Trigger);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 1);
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 1);
+    }
+
+    #[test]
+    fn on_event_criteria() {
+        fn read_system(mut reader: EventReader<Trigger>, mut counter: ResMut<u32>) {
+            *counter += reader.iter().count() as u32;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.init_resource::<Events<Trigger>>();
+
+        let mut stage = SystemStage::sequential().with_run_criteria(on_event::<Trigger>);
+        stage.add_system(read_system);
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 0);
+
+        let mut events = world.resource_mut::<Events<Trigger>>();
+        events.send(Trigger);
+        events.send(Trigger);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 2);
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 2);
+
+        world.resource_mut::<Events<Trigger>>().send(Trigger);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 3);
+    }
+
+    #[test]
+    fn local_criteria() {
+        fn every_other(mut run: Local<bool>) -> ShouldRun {
+            *run = !*run;
+            (*run).into()
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+
+        let mut stage = SystemStage::sequential().with_run_criteria(every_other);
+        stage.add_system(count_system);
+
+        for _ in 0..4 {
+            stage.run(&mut world);
+        }
+
+        assert_eq!(*world.resource::<u32>(), 2);
+    }
+}
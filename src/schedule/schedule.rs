@@ -87,6 +87,7 @@ pub enum DefaultStage {
 pub struct Schedule {
     stages: HashMap<StageLabelId, Box<dyn Stage>>,
     stage_order: Vec<StageLabelId>,
+    stage_run_criteria: HashMap<StageLabelId, RunCriteria>,
     run_criteria: RunCriteria,
 }
 
@@ -104,6 +105,7 @@ impl Schedule {
         Self {
             stages: HashMap::default(),
             stage_order: Vec::new(),
+            stage_run_criteria: HashMap::default(),
             run_criteria: RunCriteria::default(),
         }
     }
@@ -188,6 +190,42 @@ impl Schedule {
         self
     }
 
+    /// Sets the run criteria for the stage with the given `label`.
+    ///
+    /// When the criteria returns [`ShouldRun::No`] the whole stage is skipped.
+    ///
+    /// # Panics
+    /// - The stage does not exist.
+    #[track_caller]
+    pub fn set_stage_run_criteria<Marker>(
+        &mut self,
+        label: impl StageLabel,
+        run_criteria: impl IntoRunCriteria<Marker>,
+    ) -> &mut Self {
+        let id = label.label();
+
+        if !self.stages.contains_key(&id) {
+            panic!("Stage with label `{}` does not exist", id);
+        }
+
+        (self.stage_run_criteria).insert(id, run_criteria.into_run_criteria());
+        self
+    }
+
+    /// Sets the run criteria for the stage with the given `label`.
+    ///
+    /// # Panics
+    /// - The stage does not exist.
+    #[track_caller]
+    pub fn with_stage_run_criteria<Marker>(
+        mut self,
+        label: impl StageLabel,
+        run_criteria: impl IntoRunCriteria<Marker>,
+    ) -> Self {
+        self.set_stage_run_criteria(label, run_criteria);
+        self
+    }
+
     fn push_stage_internal(&mut self, label: impl StageLabel, stage: impl Stage) -> &mut Self {
         let id = label.label();
 
@@ -383,6 +421,13 @@ impl Schedule {
 
     /// Adds [`Events::update_system`] to [`DefaultStage::First`].
     /// If the stage does not exist, this function does nothing.
+    ///
+    /// Adding the same event more than once is a no-op, so the events are still kept for two
+    /// runs of the schedule.
+    ///
+    /// Events sent before the schedule is first run, e.g. by a separate startup schedule, are
+    /// readable by every system in the first run. Only one schedule should update a given event,
+    /// since every update rotates the double buffer.
     pub fn add_event<E: Event>(&mut self) {
         if let Some(stage) = self.get_stage_mut::<SystemStage>(DefaultStage::First) {
             if !stage.has_system(UpdateEventsSystem::<E>::new()) {
@@ -395,36 +440,103 @@ impl Schedule {
         }
     }
 
-    /// Runs the schedule once.
-    pub fn run_once(&mut self, world: &mut World) {
+    /// Runs every stage of the schedule once, without checking change ticks or clearing
+    /// trackers.
+    ///
+    /// Returns `false` if the schedule's run criteria prevented it from running.
+    pub fn run_stages(&mut self, world: &mut World) -> bool {
         match self.run_criteria.should_run(world) {
             ShouldRun::Yes => {}
-            ShouldRun::No => return,
+            ShouldRun::No => return false,
         }
 
         for stage_id in &self.stage_order {
             #[cfg(feature = "tracing")]
             let _guard = tracing::info_span!("stage", name = stage_id.to_string()).entered();
 
+            if let Some(run_criteria) = self.stage_run_criteria.get_mut(stage_id) {
+                if run_criteria.should_run(world) == ShouldRun::No {
+                    continue;
+                }
+            }
+
             let stage = self.stages.get_mut(stage_id).unwrap();
             stage.run(world);
         }
 
-        world.check_change_ticks();
-        world.clear_trackers();
+        true
+    }
+
+    /// Runs only the stage with the given `label`, without checking change ticks or clearing
+    /// trackers, so change detection stays consistent for the next full run.
+    ///
+    /// Returns `false` if the stage's run criteria prevented it from running. The run criteria
+    /// of the schedule itself is not checked.
+    ///
+    /// # Panics
+    /// - A stage with the given `label` does not exist.
+    #[track_caller]
+    pub fn run_stage(&mut self, label: impl StageLabel, world: &mut World) -> bool {
+        let id = label.label();
+        let stage = if let Some(stage) = self.stages.get_mut(&id) {
+            stage
+        } else {
+            panic!("Stage with label `{}` does not exist", id);
+        };
+
+        if let Some(run_criteria) = self.stage_run_criteria.get_mut(&id) {
+            if run_criteria.should_run(world) == ShouldRun::No {
+                return false;
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        let _guard = tracing::info_span!("stage", name = id.to_string()).entered();
+
+        stage.run(world);
+
+        true
+    }
+
+    /// Eagerly initializes every stage in the schedule, see [`Stage::initialize`].
+    pub fn initialize(&mut self, world: &mut World) {
+        for stage_id in &self.stage_order {
+            let stage = self.stages.get_mut(stage_id).unwrap();
+            stage.initialize(world);
+        }
+    }
+
+    /// Runs the schedule once, then checks change ticks and clears trackers.
+    pub fn run_once(&mut self, world: &mut World) {
+        if self.run_stages(world) {
+            world.check_change_ticks();
+            world.clear_trackers();
+        }
     }
 }
 
+/// A nested [`Schedule`] only runs its stages, the outermost [`Schedule::run_once`] is
+/// responsible for checking change ticks and clearing trackers.
 impl Stage for Schedule {
+    fn initialize(&mut self, world: &mut World) {
+        Schedule::initialize(self, world);
+    }
+
     fn run(&mut self, world: &mut World) {
-        self.run_once(world);
+        self.run_stages(world);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate as shiv;
-    use crate::schedule::{DefaultStage, Schedule, StageLabel, SystemStage};
+    use crate::{
+        event::{EventReader, EventWriter, Events},
+        query::{Changed, Query},
+        schedule::{DefaultStage, Schedule, ShouldRun, StageLabel, StageLabelId, SystemStage},
+        system::{Res, ResMut},
+        world::World,
+    };
 
     #[derive(StageLabel)]
     pub struct TestStage;
@@ -464,4 +576,193 @@ mod tests {
         let mut schedule = Schedule::new();
         schedule.add_stage_after(DefaultStage::Last, TestStage, SystemStage::parallel());
     }
+
+    #[test]
+    fn run_single_stage() {
+        #[derive(StageLabel)]
+        enum SingleStage {
+            A,
+            B,
+        }
+
+        fn increment_u32(mut counter: ResMut<u32>) {
+            *counter += 1;
+        }
+
+        fn increment_u64(mut counter: ResMut<u64>) {
+            *counter += 1;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.insert_resource(0u64);
+
+        let mut schedule = Schedule::new()
+            .with_stage(SingleStage::A, SystemStage::sequential())
+            .with_stage(SingleStage::B, SystemStage::sequential())
+            .with_stage_run_criteria(SingleStage::B, || ShouldRun::No);
+        schedule.add_system_to_stage(SingleStage::A, increment_u32);
+        schedule.add_system_to_stage(SingleStage::B, increment_u64);
+
+        let last_change_tick = world.last_change_tick();
+        assert!(schedule.run_stage(SingleStage::A, &mut world));
+        assert!(!schedule.run_stage(SingleStage::B, &mut world));
+
+        assert_eq!(*world.resource::<u32>(), 1);
+        assert_eq!(*world.resource::<u64>(), 0);
+        assert_eq!(world.last_change_tick(), last_change_tick);
+    }
+
+    #[test]
+    fn nested_schedule_keeps_trackers() {
+        #[derive(StageLabel)]
+        enum OuterStage {
+            A,
+            B,
+            C,
+        }
+
+        fn increment_system(mut query: Query<&mut i32>) {
+            for mut value in query.iter_mut() {
+                *value += 1;
+            }
+        }
+
+        fn noop_system() {}
+
+        fn detect_system(world: &mut World) {
+            let query = world.query_filtered::<&i32, Changed<i32>>();
+            let changed = query.iter(world).count();
+            world.insert_resource(changed);
+        }
+
+        let mut nested = Schedule::empty().with_stage(TestStage, SystemStage::sequential());
+        nested.add_system_to_stage(TestStage, noop_system);
+
+        let mut schedule = Schedule::empty()
+            .with_stage(OuterStage::A, SystemStage::sequential())
+            .with_stage(OuterStage::B, nested)
+            .with_stage(OuterStage::C, SystemStage::sequential());
+        schedule.add_system_to_stage(OuterStage::A, increment_system);
+        schedule.add_system_to_stage(OuterStage::C, detect_system);
+
+        let mut world = World::new();
+        world.spawn().insert(0i32);
+
+        schedule.run_once(&mut world);
+        schedule.run_once(&mut world);
+
+        assert_eq!(*world.resource::<usize>(), 1);
+    }
+
+    #[test]
+    fn add_event_twice() {
+        struct Ping;
+
+        let mut schedule = Schedule::new();
+        schedule.add_event::<Ping>();
+        schedule.add_event::<Ping>();
+
+        let mut world = World::new();
+        world.init_resource::<Events<Ping>>();
+        world.resource_mut::<Events<Ping>>().send(Ping);
+
+        schedule.run_once(&mut world);
+        assert_eq!(world.resource::<Events<Ping>>().len(), 1);
+
+        schedule.run_once(&mut world);
+        assert!(world.resource::<Events<Ping>>().is_empty());
+    }
+
+    #[test]
+    fn startup_events() {
+        struct Ping;
+
+        fn send_system(mut writer: EventWriter<Ping>) {
+            writer.send(Ping);
+        }
+
+        fn read_system(mut reader: EventReader<Ping>, mut counter: ResMut<u32>) {
+            *counter += reader.iter().count() as u32;
+        }
+
+        // only the main schedule updates events
+        let mut startup = Schedule::new().with_stage(TestStage, SystemStage::sequential());
+        startup.add_system_to_stage(TestStage, send_system);
+
+        let mut schedule = Schedule::new().with_stage(TestStage, SystemStage::sequential());
+        schedule.add_event::<Ping>();
+        schedule.add_system_to_stage(TestStage, read_system);
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.init_resource::<Events<Ping>>();
+        world.resource_mut::<Events<Ping>>().send(Ping);
+
+        startup.run_once(&mut world);
+        schedule.run_once(&mut world);
+        assert_eq!(*world.resource::<u32>(), 2);
+
+        schedule.run_once(&mut world);
+        assert_eq!(*world.resource::<u32>(), 2);
+    }
+
+    #[test]
+    fn stage_run_criteria() {
+        struct Online(bool);
+
+        fn is_online(online: Res<Online>) -> ShouldRun {
+            online.0.into()
+        }
+
+        fn count_system(mut counter: ResMut<u32>) {
+            *counter += 1;
+        }
+
+        let mut schedule = Schedule::new()
+            .with_stage(TestStage, SystemStage::sequential())
+            .with_stage_run_criteria(TestStage, is_online);
+        schedule.add_system_to_stage(TestStage, count_system);
+        schedule.add_system_to_stage(DefaultStage::Last, count_system);
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.insert_resource(Online(false));
+
+        schedule.run_once(&mut world);
+        assert_eq!(*world.resource::<u32>(), 1);
+
+        world.resource_mut::<Online>().0 = true;
+        schedule.run_once(&mut world);
+        assert_eq!(*world.resource::<u32>(), 3);
+    }
+
+    #[test]
+    fn runtime_stage_label() {
+        #[derive(StageLabel)]
+        enum ConfigStage {
+            A,
+            B,
+        }
+
+        fn count_system(mut counter: ResMut<u32>) {
+            *counter += 1;
+        }
+
+        let labels: Vec<StageLabelId> = vec![ConfigStage::A.label(), ConfigStage::B.label()];
+
+        let mut schedule = Schedule::empty()
+            .with_stage(ConfigStage::A, SystemStage::sequential())
+            .with_stage(ConfigStage::B, SystemStage::sequential());
+
+        for &label in labels.iter() {
+            schedule.add_system_to_stage(label, count_system);
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        schedule.run_once(&mut world);
+
+        assert_eq!(*world.resource::<u32>(), 2);
+    }
 }
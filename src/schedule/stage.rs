@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use ahash::HashSet;
 use downcast_rs::{impl_downcast, Downcast};
 use hyena::TaskPool;
@@ -9,11 +11,18 @@ use crate::{
 };
 
 use super::{
-    IntoRunCriteria, IntoSystemDescriptor, ParallelExecutor, RunCriteria, Schedule,
-    SequentialExecutor, ShouldRun, SystemContainer, SystemExecutor, SystemLabel,
+    IntoRunCriteria, IntoSystemDescriptor, IntoSystemLabel, ParallelExecutor, RunCriteria,
+    Schedule, SequentialExecutor, ShouldRun, SystemContainer, SystemExecutor,
 };
 
 pub trait Stage: Downcast + Send + Sync {
+    /// Eagerly initializes the stage for `world`.
+    ///
+    /// Stages are otherwise initialized lazily when run, calling this makes errors like
+    /// conflicting system access surface before the first run.
+    #[inline]
+    fn initialize(&mut self, _world: &mut World) {}
+
     fn run(&mut self, world: &mut World);
 }
 
@@ -35,9 +44,12 @@ impl_downcast!(Stage);
 pub struct SystemStage {
     world_id: Option<WorldId>,
     executor: Box<dyn SystemExecutor>,
+    /// An executor per batch of parallel systems, when batched, see [`SystemExecutor::fork`].
+    batch_executors: Vec<Box<dyn SystemExecutor>>,
     run_criteria: RunCriteria,
     exclusive_systems: Vec<SystemContainer>,
     parallel_systems: Vec<SystemContainer>,
+    parallel_batches: Vec<Range<usize>>,
     uninitialized_exclusive: Vec<usize>,
     uninitialized_parallel: Vec<usize>,
     systems_modified: bool,
@@ -49,9 +61,11 @@ impl SystemStage {
         Self {
             world_id: None,
             executor: Box::new(executor),
+            batch_executors: Vec::new(),
             run_criteria: RunCriteria::default(),
             exclusive_systems: Vec::new(),
             parallel_systems: Vec::new(),
+            parallel_batches: Vec::new(),
             uninitialized_exclusive: Vec::new(),
             uninitialized_parallel: Vec::new(),
             systems_modified: true,
@@ -71,6 +85,12 @@ impl SystemStage {
         Self::new(ParallelExecutor::new_with_task_pool(task_pool))
     }
 
+    /// Adds a system to the stage.
+    ///
+    /// Ordering is resolved from labels when the stage is next run, so `before` and `after`
+    /// may refer to systems that haven't been added yet. Exclusive and parallel systems are
+    /// ordered together, an exclusive system runs after the parallel systems it's ordered after,
+    /// and their buffers, have finished.
     pub fn add_system<Params>(&mut self, system: impl IntoSystemDescriptor<Params>) {
         let descriptor = system.into_descriptor();
         let container = SystemContainer::from_descriptor(descriptor);
@@ -94,8 +114,8 @@ impl SystemStage {
         self
     }
 
-    pub fn has_system(&self, label: impl SystemLabel) -> bool {
-        let label = label.label();
+    pub fn has_system<Marker>(&self, label: impl IntoSystemLabel<Marker>) -> bool {
+        let label = label.into_system_label();
         self.has_exclusive_system(&label) || self.has_parallel_system(&label)
     }
 
@@ -122,19 +142,38 @@ impl SystemStage {
     }
 
     pub fn apply_buffers(&mut self, world: &mut World) {
-        for container in self.parallel_systems.iter_mut() {
-            #[cfg(feature = "tracing")]
-            let _guard = tracing::info_span!("apply", name = container.name()).entered();
-
-            container.system_mut().apply(world);
-        }
+        self.apply_parallel_buffers(0..self.parallel_systems.len(), world);
     }
 
+    /// Returns the parallel systems of the stage, in the order they're scheduled once the
+    /// stage has been updated.
     #[inline]
     pub fn parallel_systems(&self) -> &[SystemContainer] {
         &self.parallel_systems
     }
 
+    /// Returns the exclusive systems of the stage, in the order they're run once the stage has
+    /// been updated.
+    #[inline]
+    pub fn exclusive_systems(&self) -> &[SystemContainer] {
+        &self.exclusive_systems
+    }
+
+    /// Returns the ranges of [`SystemStage::parallel_systems`] that are run together, in order.
+    #[inline]
+    pub fn parallel_batches(&self) -> &[Range<usize>] {
+        &self.parallel_batches
+    }
+
+    /// Returns an iterator over every system in the stage, both parallel and exclusive.
+    ///
+    /// Useful for toggling systems with [`SystemContainer::set_enabled`].
+    #[inline]
+    pub fn systems_mut(&mut self) -> impl Iterator<Item = &mut SystemContainer> {
+        let parallel = self.parallel_systems.iter_mut();
+        parallel.chain(self.exclusive_systems.iter_mut())
+    }
+
     fn validate_world(&mut self, world: &mut World) {
         if let Some(ref mut world_id) = self.world_id {
             if *world_id != world.id() {
@@ -184,12 +223,106 @@ impl SystemStage {
         }
     }
 
+    /// Sorts parallel and exclusive systems by their labels.
+    ///
+    /// Parallel systems are split into batches, batch `n` runs before exclusive system `n`, so
+    /// that dependencies crossing between parallel and exclusive systems are respected.
     fn rebuild_systems(&mut self) {
-        Self::rebuild_dependency_graph(&mut self.parallel_systems);
-        Self::rebuild_dependency_graph(&mut self.exclusive_systems);
+        let parallel_len = self.parallel_systems.len();
+        let exclusive_len = self.exclusive_systems.len();
+
+        let systems = (self.parallel_systems.iter())
+            .chain(self.exclusive_systems.iter())
+            .collect::<Vec<_>>();
+        let (graph, sorted) = Self::sort_systems(&systems);
+
+        // the batch of a parallel system, or the index of an exclusive system
+        let mut batch = vec![0; systems.len()];
+        let mut exclusive_order = Vec::with_capacity(exclusive_len);
+
+        for &node in sorted.iter() {
+            if node >= parallel_len {
+                batch[node] = exclusive_order.len();
+                exclusive_order.push(node - parallel_len);
+            } else {
+                let dependencies = graph[node].iter().map(|&dependency| {
+                    if dependency >= parallel_len {
+                        batch[dependency] + 1
+                    } else {
+                        batch[dependency]
+                    }
+                });
+
+                batch[node] = dependencies.max().unwrap_or(0);
+            }
+        }
+
+        // a stable sort keeps the topological order within each batch
+        let mut parallel_order = (sorted.iter().copied())
+            .filter(|&node| node < parallel_len)
+            .collect::<Vec<_>>();
+        parallel_order.sort_by_key(|&node| batch[node]);
+
+        let mut position = vec![0; parallel_len];
+        for (index, &node) in parallel_order.iter().enumerate() {
+            position[node] = index;
+        }
+
+        let mut batches = Vec::with_capacity(exclusive_len + 1);
+        let mut start = 0;
+        for index in 0..=exclusive_len {
+            let len = parallel_order[start..]
+                .iter()
+                .take_while(|&&node| batch[node] == index)
+                .count();
+
+            batches.push(start..start + len);
+            start += len;
+        }
+
+        // dependencies in earlier batches or on exclusive systems are satisfied by the batch
+        // order, so only dependencies within the same batch are kept
+        let dependencies = parallel_order.iter().map(|&node| {
+            let start = batches[batch[node]].start;
+
+            let mut dependencies = (graph[node].iter().copied())
+                .filter(|&dependency| dependency < parallel_len)
+                .filter(|&dependency| batch[dependency] == batch[node])
+                .map(|dependency| position[dependency] - start)
+                .collect::<Vec<_>>();
+            dependencies.sort_unstable();
+
+            dependencies
+        });
+        let dependencies = dependencies.collect::<Vec<_>>();
+
+        let mut parallel = self
+            .parallel_systems
+            .drain(..)
+            .map(Some)
+            .collect::<Vec<_>>();
+        for (&node, dependencies) in parallel_order.iter().zip(dependencies) {
+            let mut container = parallel[node].take().unwrap();
+            *container.dependencies_mut() = dependencies;
+            self.parallel_systems.push(container);
+        }
+
+        let mut exclusive = self
+            .exclusive_systems
+            .drain(..)
+            .map(Some)
+            .collect::<Vec<_>>();
+        for index in exclusive_order {
+            let mut container = exclusive[index].take().unwrap();
+            container.dependencies_mut().clear();
+            self.exclusive_systems.push(container);
+        }
+
+        self.parallel_batches = batches;
     }
 
-    fn rebuild_dependency_graph(systems: &mut Vec<SystemContainer>) {
+    /// Returns the dependencies of each system and a topological order of `systems`.
+    fn sort_systems(systems: &[&SystemContainer]) -> (Vec<HashSet<usize>>, Vec<usize>) {
         let mut labels = HashMap::<SystemLabelId, Vec<usize>>::default();
 
         for (index, container) in systems.iter().enumerate() {
@@ -198,27 +331,25 @@ impl SystemStage {
             }
         }
 
-        let mut graph = HashMap::<usize, HashSet<usize>>::default();
+        let mut graph = vec![HashSet::<usize>::default(); systems.len()];
 
         for (index, container) in systems.iter().enumerate() {
-            let dependencies = graph.entry(index).or_default();
-
             for &label in container.after() {
                 for &dependency in labels.get(&label).unwrap_or(&Vec::new()) {
-                    dependencies.insert(dependency);
+                    graph[index].insert(dependency);
                 }
             }
 
             for &label in container.before() {
                 for &dependant in labels.get(&label).unwrap_or(&Vec::new()) {
-                    graph.entry(dependant).or_default().insert(index);
+                    graph[dependant].insert(index);
                 }
             }
         }
 
         fn visit(
             node: usize,
-            graph: &HashMap<usize, HashSet<usize>>,
+            graph: &[HashSet<usize>],
             sorted: &mut Vec<usize>,
             current: &mut Vec<usize>,
             unvisited: &mut HashSet<usize>,
@@ -231,7 +362,7 @@ impl SystemStage {
 
             current.push(node);
 
-            for &dependency in graph.get(&node).unwrap() {
+            for &dependency in graph[node].iter() {
                 if visit(dependency, graph, sorted, current, unvisited) {
                     return true;
                 }
@@ -245,9 +376,10 @@ impl SystemStage {
 
         let mut sorted = Vec::with_capacity(graph.len());
         let mut current = Vec::with_capacity(graph.len());
-        let mut unvisited = graph.keys().copied().collect::<HashSet<_>>();
+        let mut unvisited = (0..graph.len()).collect::<HashSet<_>>();
 
-        while let Some(index) = unvisited.iter().next().copied() {
+        // visiting in insertion order keeps the order of unrelated systems stable
+        for index in 0..graph.len() {
             if visit(index, &graph, &mut sorted, &mut current, &mut unvisited) {
                 let names = current
                     .iter()
@@ -262,32 +394,29 @@ impl SystemStage {
             }
         }
 
-        for (index, system) in systems.iter_mut().enumerate() {
-            system.dependencies_mut().clear();
+        (graph, sorted)
+    }
 
-            for &dependency in graph.get(&index).unwrap() {
-                let dependency = sorted.iter().position(|&i| i == dependency).unwrap();
-                system.dependencies_mut().push(dependency);
-            }
-        }
+    /// Returns `true` if exclusive systems are interleaved with more than one batch of
+    /// parallel systems.
+    #[inline]
+    fn is_batched(&self) -> bool {
+        let batches = self.parallel_batches.iter();
+        batches.filter(|batch| !batch.is_empty()).count() > 1
+    }
 
-        let mut temp = systems.drain(..).map(Some).collect::<Vec<_>>();
+    fn apply_parallel_buffers(&mut self, batch: Range<usize>, world: &mut World) {
+        for container in self.parallel_systems[batch].iter_mut() {
+            #[cfg(feature = "tracing")]
+            let _guard = tracing::info_span!("apply", name = container.name()).entered();
 
-        for index in sorted {
-            systems.push(temp[index].take().unwrap());
+            container.system_mut().apply(world);
         }
     }
 }
 
-impl Stage for SystemStage {
-    fn run(&mut self, world: &mut World) {
-        self.validate_world(world);
-
-        match self.run_criteria.should_run(world) {
-            ShouldRun::Yes => {}
-            ShouldRun::No => return,
-        }
-
+impl SystemStage {
+    fn update_systems(&mut self, world: &mut World) {
         if self.systems_modified {
             self.systems_modified = false;
             self.executor_modified = false;
@@ -295,12 +424,57 @@ impl Stage for SystemStage {
             self.initialize_systems(world);
             self.rebuild_systems();
 
-            self.executor.systems_changed(&self.parallel_systems);
+            self.executor_changed();
         } else if self.executor_modified {
             self.executor_modified = false;
 
+            self.executor_changed();
+        }
+    }
+
+    /// Batched stages notify an executor per batch instead, see [`SystemExecutor::fork`].
+    #[inline]
+    fn executor_changed(&mut self) {
+        self.batch_executors.clear();
+
+        if !self.is_batched() {
             self.executor.systems_changed(&self.parallel_systems);
+            return;
+        }
+
+        for batch in self.parallel_batches.iter() {
+            let mut executor = match self.executor.fork() {
+                Some(executor) => executor,
+                None => {
+                    self.batch_executors.clear();
+                    return;
+                }
+            };
+
+            executor.systems_changed(&self.parallel_systems[batch.clone()]);
+            self.batch_executors.push(executor);
         }
+    }
+}
+
+impl Stage for SystemStage {
+    /// Initializes all systems added to the stage and builds the dependency graph.
+    ///
+    /// Systems added after this are initialized when the stage is next run.
+    fn initialize(&mut self, world: &mut World) {
+        self.validate_world(world);
+        self.update_systems(world);
+    }
+
+    fn run(&mut self, world: &mut World) {
+        self.validate_world(world);
+
+        match self.run_criteria.should_run(world) {
+            ShouldRun::Yes => {}
+            ShouldRun::No => return,
+        }
+
+        self.update_systems(world);
 
         for container in self.exclusive_systems.iter_mut() {
             container.run_criteria_mut().run(world);
@@ -310,23 +484,42 @@ impl Stage for SystemStage {
             container.run_criteria_mut().run(world);
         }
 
-        // SAFETY:
-        // - `world` was validated earlier
-        unsafe { self.executor.run_systems(&mut self.parallel_systems, world) };
+        let batched = self.is_batched();
 
-        self.apply_buffers(world);
+        for index in 0..self.parallel_batches.len() {
+            let batch = self.parallel_batches[index].clone();
 
-        for container in self.exclusive_systems.iter_mut() {
-            if container.should_run() {
-                #[cfg(feature = "tracing")]
-                let guard = tracing::info_span!("system", system = container.name()).entered();
-                container.system_mut().run((), world);
-                #[cfg(feature = "tracing")]
-                drop(guard);
-
-                #[cfg(feature = "tracing")]
-                let _guard = tracing::info_span!("apply", system = container.name()).entered();
-                container.system_mut().apply(world);
+            if !batch.is_empty() {
+                let systems = &mut self.parallel_systems[batch.clone()];
+
+                let executor = match self.batch_executors.get_mut(index) {
+                    Some(executor) => executor,
+                    None if batched => {
+                        self.executor.systems_changed(systems);
+                        &mut self.executor
+                    }
+                    None => &mut self.executor,
+                };
+
+                // SAFETY:
+                // - `world` was validated earlier
+                unsafe { executor.run_systems(systems, world) };
+
+                self.apply_parallel_buffers(batch, world);
+            }
+
+            if let Some(container) = self.exclusive_systems.get_mut(index) {
+                if container.should_run() {
+                    #[cfg(feature = "tracing")]
+                    let guard = tracing::info_span!("system", system = container.name()).entered();
+                    container.system_mut().run((), world);
+                    #[cfg(feature = "tracing")]
+                    drop(guard);
+
+                    #[cfg(feature = "tracing")]
+                    let _guard = tracing::info_span!("apply", system = container.name()).entered();
+                    container.system_mut().apply(world);
+                }
             }
         }
 
@@ -336,13 +529,22 @@ impl Stage for SystemStage {
 
 #[cfg(test)]
 mod tests {
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
 
     use crate as shiv;
     use crate::{
-        query::Query,
-        schedule::{IntoSystemDescriptor, SystemLabel},
-        system::ResMut,
+        query::{Query, With, Without},
+        schedule::{
+            IntoSystemDescriptor, SequentialExecutor, ShouldRun, SystemContainer, SystemExecutor,
+            SystemLabel,
+        },
+        system::{Commands, Res, ResMut},
         world::World,
     };
 
@@ -394,6 +596,23 @@ mod tests {
         stage.run(&mut world);
     }
 
+    #[test]
+    fn run_after_system() {
+        let mut world = World::new();
+        world.init_resource::<u32>();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(system_c.after(system_b));
+        stage.add_system(system_b.after(system_a));
+        stage.add_system(system_a);
+
+        assert!(stage.has_system(system_b));
+        assert!(!stage.has_system(TestSystem::A));
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 3);
+    }
+
     #[test]
     fn run_ordered() {
         let mut world = World::new();
@@ -422,6 +641,169 @@ mod tests {
         stage.run(&mut world);
     }
 
+    #[test]
+    fn add_after_run() {
+        let mut world = World::new();
+        world.insert_resource(2u32);
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(system_c.after(TestSystem::B));
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 3);
+
+        *world.resource_mut::<u32>() = 0;
+        stage.add_system(system_a.label(TestSystem::A).before(TestSystem::B));
+        stage.add_system(system_b.label(TestSystem::B));
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 3);
+    }
+
+    #[test]
+    fn interleave_exclusive() {
+        type Order = Vec<&'static str>;
+
+        fn parallel_a(mut order: ResMut<Order>, mut commands: Commands) {
+            order.push("a");
+            commands.spawn().insert(0i32);
+        }
+
+        fn exclusive_b(world: &mut World) {
+            let query = world.query::<&i32>();
+            assert_eq!(query.iter(world).count(), 1, "commands weren't applied");
+
+            world.resource_mut::<Order>().push("b");
+        }
+
+        fn parallel_c(mut order: ResMut<Order>) {
+            order.push("c");
+        }
+
+        fn exclusive_d(world: &mut World) {
+            world.resource_mut::<Order>().push("d");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(exclusive_d.after(TestSystem::C));
+        stage.add_system(parallel_c.label(TestSystem::C));
+        stage.add_system(
+            exclusive_b
+                .label(TestSystem::B)
+                .after(TestSystem::A)
+                .before(TestSystem::C),
+        );
+        stage.add_system(parallel_a.label(TestSystem::A));
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<Order>(), vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn batch_executors() {
+        #[derive(Clone, Default)]
+        struct CountingExecutor {
+            changed: Arc<AtomicUsize>,
+        }
+
+        impl SystemExecutor for CountingExecutor {
+            fn systems_changed(&mut self, _systems: &[SystemContainer]) {
+                self.changed.fetch_add(1, Ordering::Relaxed);
+            }
+
+            fn fork(&self) -> Option<Box<dyn SystemExecutor>> {
+                Some(Box::new(self.clone()))
+            }
+
+            unsafe fn run_systems(&mut self, systems: &mut [SystemContainer], world: &mut World) {
+                unsafe { SequentialExecutor.run_systems(systems, world) };
+            }
+        }
+
+        fn parallel_a() {}
+        fn exclusive_b(_world: &mut World) {}
+        fn parallel_c() {}
+
+        let executor = CountingExecutor::default();
+        let changed = executor.changed.clone();
+
+        let mut world = World::new();
+        let mut stage = SystemStage::new(executor);
+        stage.add_system(parallel_a.label(TestSystem::A));
+        stage.add_system(exclusive_b.label(TestSystem::B).after(TestSystem::A));
+        stage.add_system(parallel_c.after(TestSystem::B));
+
+        for _ in 0..3 {
+            stage.run(&mut world);
+        }
+
+        // each batch executor is only notified once
+        assert_eq!(stage.parallel_batches().len(), 2);
+        assert_eq!(changed.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn run_once_per_world() {
+        fn increment(mut counter: ResMut<u32>) {
+            *counter += 1;
+        }
+
+        let mut stage = SystemStage::sequential().with_run_criteria(ShouldRun::once);
+        stage.add_system(increment);
+        stage.add_system(increment.with_run_criteria(ShouldRun::once));
+
+        let mut world_a = World::new();
+        world_a.init_resource::<u32>();
+        stage.run(&mut world_a);
+        stage.run(&mut world_a);
+        assert_eq!(*world_a.resource::<u32>(), 2);
+
+        let mut world_b = World::new();
+        world_b.init_resource::<u32>();
+        stage.run(&mut world_b);
+        stage.run(&mut world_b);
+        assert_eq!(*world_b.resource::<u32>(), 2);
+    }
+
+    #[test]
+    fn disable_system() {
+        let mut world = World::new();
+        world.init_resource::<u32>();
+        world.init_resource::<bool>();
+
+        // runs after the disabled system, which must not stall it
+        fn system_d(counter: Res<u32>, mut has_run: ResMut<bool>) {
+            assert!(*counter > 0);
+            *has_run = true;
+        }
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(system_a.label(TestSystem::A));
+        stage.add_system(system_b.label(TestSystem::B).after(TestSystem::A));
+        stage.add_system(system_d.after(TestSystem::B));
+
+        for container in stage.systems_mut() {
+            if container.labels().contains(&TestSystem::B.label()) {
+                container.set_enabled(false);
+            }
+        }
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 1);
+        assert!(*world.resource::<bool>());
+
+        *world.resource_mut::<u32>() = 0;
+        for container in stage.systems_mut() {
+            container.set_enabled(true);
+        }
+
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<u32>(), 2);
+    }
+
     #[test]
     #[should_panic]
     fn fail_cycle() {
@@ -484,4 +866,121 @@ mod tests {
 
         stage.run(&mut world);
     }
+
+    #[test]
+    fn parallel_disjoint_filters() {
+        fn with_bool(mut query: Query<&mut i32, With<bool>>) {
+            for mut i in query.iter_mut() {
+                *i += 1;
+            }
+        }
+
+        fn without_bool(mut query: Query<&mut i32, Without<bool>>) {
+            for mut i in query.iter_mut() {
+                *i -= 1;
+            }
+        }
+
+        fn unfiltered(_query: Query<&mut i32>) {}
+
+        let mut world = World::new();
+        let a = world.spawn().insert(0i32).insert(true).entity();
+        let b = world.spawn().insert(0i32).entity();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(with_bool);
+        stage.add_system(without_bool);
+        stage.add_system(unfiltered);
+        stage.initialize(&mut world);
+
+        // the parallel executor runs systems together when their access is compatible
+        let access = |name: &str| {
+            let mut systems = stage.parallel_systems().iter();
+            let system = systems.find(|system| system.name().ends_with(name));
+            system.unwrap().access()
+        };
+
+        assert!(access("::with_bool").is_compatible(access("::without_bool")));
+        assert!(!access("::with_bool").is_compatible(access("::unfiltered")));
+        assert!(!access("::without_bool").is_compatible(access("::unfiltered")));
+
+        stage.run(&mut world);
+        assert_eq!(world.get::<i32>(a), Some(&1));
+        assert_eq!(world.get::<i32>(b), Some(&-1));
+    }
+
+    #[test]
+    fn parallel_read_all() {
+        static READING: AtomicUsize = AtomicUsize::new(0);
+        static WRITING: AtomicUsize = AtomicUsize::new(0);
+        static OVERLAPPED: AtomicUsize = AtomicUsize::new(0);
+
+        fn run_while(running: &AtomicUsize, conflicting: &AtomicUsize) {
+            running.fetch_add(1, Ordering::SeqCst);
+            if conflicting.load(Ordering::SeqCst) > 0 {
+                OVERLAPPED.fetch_add(1, Ordering::SeqCst);
+            }
+
+            std::thread::sleep(Duration::from_millis(10));
+            running.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        fn read_world(world: &World) {
+            run_while(&READING, &WRITING);
+            assert!(world.contains_resource::<u32>());
+        }
+
+        fn write_query(mut query: Query<&mut i32>) {
+            run_while(&WRITING, &READING);
+
+            for mut i in query.iter_mut() {
+                *i += 1;
+            }
+        }
+
+        fn write_resource(mut counter: ResMut<u32>) {
+            run_while(&WRITING, &READING);
+            *counter += 1;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        let entity = world.spawn().insert(0i32).entity();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(read_world);
+        stage.add_system(write_query);
+        stage.add_system(read_world);
+        stage.add_system(write_resource);
+        stage.add_system(read_world);
+
+        stage.run(&mut world);
+
+        assert_eq!(OVERLAPPED.load(Ordering::SeqCst), 0);
+        assert_eq!(world.get::<i32>(entity), Some(&1));
+        assert_eq!(*world.resource::<u32>(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn initialize_conflict() {
+        fn conflicting(_a: Query<&i32>, _b: Query<&mut i32>) {}
+
+        let mut world = World::new();
+        let mut stage = SystemStage::sequential().with_system(conflicting);
+        stage.initialize(&mut world);
+    }
+
+    #[test]
+    fn initialize_then_add() {
+        let mut world = World::new();
+        world.insert_resource(0u32);
+
+        let mut stage = SystemStage::sequential().with_system(system_a.label(TestSystem::A));
+        stage.initialize(&mut world);
+        stage.add_system(system_b.after(TestSystem::A));
+        stage.run(&mut world);
+
+        assert_eq!(*world.resource::<u32>(), 2);
+    }
 }
@@ -1,7 +1,49 @@
-use crate::system::{BoxedSystem, System, SystemMeta};
+use crate::{
+    system::{BoxedSystem, FilteredAccessSet, System, SystemMeta},
+    world::ComponentId,
+};
 
 use super::{RunCriteriaContainer, SystemDescriptor, SystemLabelId};
 
+/// A system in a [`SystemStage`](super::SystemStage), together with its labels, ordering
+/// constraints and run criteria.
+///
+/// Containers are exposed through [`SystemStage::parallel_systems`] and
+/// [`SystemStage::exclusive_systems`], which allows tools to inspect the structure of a stage.
+///
+/// [`SystemStage::parallel_systems`]: super::SystemStage::parallel_systems
+/// [`SystemStage::exclusive_systems`]: super::SystemStage::exclusive_systems
+///
+/// # Example
+/// ```rust
+/// # use shiv::prelude::*;
+/// #[derive(SystemLabel)]
+/// struct Physics;
+///
+/// fn physics() {}
+/// fn render() {}
+///
+/// let mut world = World::new();
+/// let mut stage = SystemStage::parallel()
+///     .with_system(render.after(Physics))
+///     .with_system(physics.label(Physics));
+/// stage.initialize(&mut world);
+///
+/// let systems = stage.parallel_systems();
+/// let summary = systems.iter().map(|container| {
+///     let after = container.dependencies().iter();
+///     let after = after.map(|&index| systems[index].name()).collect::<Vec<_>>();
+///     format!("{} after {:?}", container.name(), after)
+/// });
+///
+/// for line in summary {
+///     println!("{}", line);
+/// }
+///
+/// assert!(systems[0].labels().contains(&Physics.label()));
+/// assert_eq!(systems[1].after(), &[Physics.label()]);
+/// assert_eq!(systems[1].dependencies(), &[0]);
+/// ```
 #[derive(Debug)]
 pub struct SystemContainer {
     system: BoxedSystem<(), ()>,
@@ -10,6 +52,7 @@ pub struct SystemContainer {
     after: Vec<SystemLabelId>,
     run_criteria: RunCriteriaContainer,
     dependencies: Vec<usize>,
+    enabled: bool,
 }
 
 impl SystemContainer {
@@ -22,9 +65,11 @@ impl SystemContainer {
             after: descriptor.after,
             run_criteria: RunCriteriaContainer::new(descriptor.run_criteria),
             dependencies: Vec::new(),
+            enabled: true,
         }
     }
 
+    /// Returns the [`SystemMeta`] of the system.
     #[inline]
     pub fn meta(&self) -> &SystemMeta {
         self.system.meta()
@@ -45,13 +90,28 @@ impl SystemContainer {
         self.system.as_mut()
     }
 
+    /// Returns the component access of the system.
+    ///
+    /// This is only complete once the system has been initialized.
+    #[inline]
+    pub fn access(&self) -> &FilteredAccessSet<ComponentId> {
+        &self.system.meta().access
+    }
+
+    /// Returns the indices of the systems this system must run after, resolved from
+    /// [`SystemContainer::before`] and [`SystemContainer::after`] when the stage is updated.
+    ///
+    /// Indices are relative to the start of the system's parallel batch, see
+    /// [`SystemStage::parallel_batches`](super::SystemStage::parallel_batches). Only
+    /// dependencies within the same batch are kept, since batches run in order. Exclusive
+    /// systems run in order and have no dependencies.
     #[inline]
     pub fn dependencies(&self) -> &[usize] {
         &self.dependencies
     }
 
     #[inline]
-    pub fn dependencies_mut(&mut self) -> &mut Vec<usize> {
+    pub(crate) fn dependencies_mut(&mut self) -> &mut Vec<usize> {
         &mut self.dependencies
     }
 
@@ -64,21 +124,39 @@ impl SystemContainer {
         &mut self.run_criteria
     }
 
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Sets whether the system is enabled, disabled systems are skipped when the stage is run.
+    ///
+    /// Disabled systems still take part in ordering, so systems ordered relative to them
+    /// keep their order.
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
     #[inline]
     pub fn should_run(&self) -> bool {
-        self.run_criteria.should_run().into()
+        self.enabled && self.run_criteria.should_run().into()
     }
 
+    /// Returns the labels of the system, including the label it's implicitly given based on
+    /// its type.
     #[inline]
     pub fn labels(&self) -> &[SystemLabelId] {
         &self.labels
     }
 
+    /// Returns the labels of the systems this system must run before.
     #[inline]
     pub fn before(&self) -> &[SystemLabelId] {
         &self.before
     }
 
+    /// Returns the labels of the systems this system must run after.
     #[inline]
     pub fn after(&self) -> &[SystemLabelId] {
         &self.after
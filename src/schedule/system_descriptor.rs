@@ -1,3 +1,5 @@
+use std::any::type_name;
+
 use crate::system::{BoxedSystem, IntoSystem, System};
 
 use super::{IntoRunCriteria, RunCriteria, SystemLabel, SystemLabelId};
@@ -18,7 +20,7 @@ impl SystemDescriptor {
     {
         Self {
             system: Box::new(system),
-            labels: Vec::new(),
+            labels: vec![system_type_label::<S, ()>()],
             before: Vec::new(),
             after: Vec::new(),
             run_criteria: RunCriteria::default(),
@@ -26,6 +28,56 @@ impl SystemDescriptor {
     }
 }
 
+/// Returns the implicit label given to every system created from `S`.
+#[inline]
+fn system_type_label<S, Params>() -> SystemLabelId
+where
+    S: IntoSystem<(), (), Params>,
+{
+    SystemLabelId::from_raw_parts::<S::System>(type_name::<S>(), 0)
+}
+
+/// Something that can be used as a label when ordering systems.
+///
+/// This is implemented for every [`SystemLabel`], and for systems themselves, which resolve
+/// to the label every system is implicitly given based on its type. This allows ordering
+/// against a system without labeling it.
+///
+/// # Example
+/// ```rust
+/// # use shiv::prelude::*;
+/// fn first() {}
+/// fn second() {}
+///
+/// let mut stage = SystemStage::sequential();
+/// stage.add_system(second.after(first));
+/// stage.add_system(first);
+/// ```
+pub trait IntoSystemLabel<Marker> {
+    fn into_system_label(self) -> SystemLabelId;
+}
+
+impl<T: SystemLabel> IntoSystemLabel<()> for T {
+    #[inline]
+    fn into_system_label(self) -> SystemLabelId {
+        self.label()
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct IsSystemType;
+
+impl<S, Params> IntoSystemLabel<(Params, IsSystemType)> for S
+where
+    S: IntoSystem<(), (), Params>,
+{
+    #[inline]
+    fn into_system_label(self) -> SystemLabelId {
+        system_type_label::<S, Params>()
+    }
+}
+
 pub trait IntoSystemDescriptor<Params = ()> {
     fn into_descriptor(self) -> SystemDescriptor;
 
@@ -36,9 +88,9 @@ pub trait IntoSystemDescriptor<Params = ()> {
 
     fn label(self, label: impl SystemLabel) -> SystemDescriptor;
 
-    fn before(self, label: impl SystemLabel) -> SystemDescriptor;
+    fn before<Marker>(self, label: impl IntoSystemLabel<Marker>) -> SystemDescriptor;
 
-    fn after(self, label: impl SystemLabel) -> SystemDescriptor;
+    fn after<Marker>(self, label: impl IntoSystemLabel<Marker>) -> SystemDescriptor;
 }
 
 impl<S, Params> IntoSystemDescriptor<Params> for S
@@ -49,7 +101,7 @@ where
     fn into_descriptor(self) -> SystemDescriptor {
         SystemDescriptor {
             system: Box::new(self.into_system()),
-            labels: Vec::new(),
+            labels: vec![system_type_label::<S, Params>()],
             before: Vec::new(),
             after: Vec::new(),
             run_criteria: RunCriteria::default(),
@@ -74,16 +126,16 @@ where
     }
 
     #[inline]
-    fn before(self, label: impl SystemLabel) -> SystemDescriptor {
+    fn before<Marker>(self, label: impl IntoSystemLabel<Marker>) -> SystemDescriptor {
         let mut descriptor = self.into_descriptor();
-        descriptor.before.push(label.label());
+        descriptor.before.push(label.into_system_label());
         descriptor
     }
 
     #[inline]
-    fn after(self, label: impl SystemLabel) -> SystemDescriptor {
+    fn after<Marker>(self, label: impl IntoSystemLabel<Marker>) -> SystemDescriptor {
         let mut descriptor = self.into_descriptor();
-        descriptor.after.push(label.label());
+        descriptor.after.push(label.into_system_label());
         descriptor
     }
 }
@@ -110,14 +162,14 @@ impl IntoSystemDescriptor for SystemDescriptor {
     }
 
     #[inline]
-    fn before(mut self, label: impl SystemLabel) -> SystemDescriptor {
-        self.before.push(label.label());
+    fn before<Marker>(mut self, label: impl IntoSystemLabel<Marker>) -> SystemDescriptor {
+        self.before.push(label.into_system_label());
         self
     }
 
     #[inline]
-    fn after(mut self, label: impl SystemLabel) -> SystemDescriptor {
-        self.after.push(label.label());
+    fn after<Marker>(mut self, label: impl IntoSystemLabel<Marker>) -> SystemDescriptor {
+        self.after.push(label.into_system_label());
         self
     }
 }
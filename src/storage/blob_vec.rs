@@ -16,12 +16,13 @@ impl BlobVec {
     #[inline]
     pub fn new(item_layout: Layout, drop: Option<unsafe fn(*mut u8)>, capacity: usize) -> BlobVec {
         if item_layout.size() == 0 {
+            // zero-sized items never need to allocate
             Self {
                 item_layout,
                 drop,
-                capacity: 0,
+                capacity: usize::MAX,
                 len: 0,
-                data: NonNull::dangling(),
+                data: dangling_with_align(item_layout.align()),
             }
         } else {
             let mut this = Self {
@@ -29,7 +30,7 @@ impl BlobVec {
                 drop,
                 capacity: 0,
                 len: 0,
-                data: NonNull::dangling(),
+                data: dangling_with_align(item_layout.align()),
             };
             this.reserve_exact(capacity);
 
@@ -71,8 +72,11 @@ impl BlobVec {
     #[inline]
     pub unsafe fn initialize_unchecked(&mut self, index: usize, value: *mut u8) {
         debug_assert!(index < self.len);
+        debug_assert_eq!(value as usize % self.item_layout.align(), 0);
 
         let ptr = unsafe { self.get_unchecked(index) };
+        debug_assert_eq!(ptr as usize % self.item_layout.align(), 0);
+
         unsafe { ptr::copy_nonoverlapping(value, ptr, self.item_layout.size()) };
     }
 
@@ -98,7 +102,7 @@ impl BlobVec {
     /// - `value` must be a valid this [`BlobVec`]
     #[inline]
     pub unsafe fn push(&mut self, value: *mut u8) {
-        self.reserve_exact(1);
+        self.reserve(1);
         let index = self.len();
         self.len += 1;
         unsafe { self.initialize_unchecked(index, value) };
@@ -113,8 +117,8 @@ impl BlobVec {
         let last = unsafe { self.get_unchecked(self.len - 1) };
         let target = unsafe { self.get_unchecked(index) };
 
-        unsafe { ptr::copy_nonoverlapping(last, ptr, self.item_layout.size()) };
-        unsafe { ptr::copy(target, last, self.item_layout.size()) };
+        unsafe { ptr::copy_nonoverlapping(target, ptr, self.item_layout.size()) };
+        unsafe { ptr::copy(last, target, self.item_layout.size()) };
 
         self.len -= 1;
     }
@@ -139,6 +143,37 @@ impl BlobVec {
         }
     }
 
+    /// Swaps the items at `a` and `b`.
+    ///
+    /// # Safety
+    /// - `a` and `b` must be in bounds
+    #[inline]
+    pub unsafe fn swap_unchecked(&mut self, a: usize, b: usize) {
+        debug_assert!(a < self.len && b < self.len);
+
+        if a != b {
+            let a = unsafe { self.get_unchecked(a) };
+            let b = unsafe { self.get_unchecked(b) };
+            unsafe { ptr::swap_nonoverlapping(a, b, self.item_layout.size()) };
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more items, growing geometrically to
+    /// amortize reallocations.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        let available = self.capacity - self.len;
+
+        if available < additional {
+            let required = self.len + additional;
+            let new_capacity = usize::max(self.capacity * 2, required).max(4);
+
+            // SAFETY: zero-sized items have a capacity of `usize::MAX`, so `available` is
+            // never less than `additional`, and `new_capacity > self.capacity`.
+            unsafe { self.grow_exact(new_capacity - self.capacity) }
+        }
+    }
+
     #[inline]
     pub fn reserve_exact(&mut self, additional: usize) {
         let available = self.capacity - self.len;
@@ -198,6 +233,13 @@ impl BlobVec {
     }
 }
 
+/// A dangling pointer aligned to `align`, so that zero-sized items are properly aligned.
+#[inline]
+fn dangling_with_align(align: usize) -> NonNull<u8> {
+    // SAFETY: `align` is a power of two, so it's never zero.
+    unsafe { NonNull::new_unchecked(align as *mut u8) }
+}
+
 #[inline]
 fn array_layout(layout: Layout, len: usize) -> Option<Layout> {
     let padded_size = layout.pad_to_align().size();
@@ -221,3 +263,82 @@ impl Drop for BlobVec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        alloc::Layout,
+        ptr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::BlobVec;
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    #[repr(align(16))]
+    struct Marker;
+
+    impl Drop for Marker {
+        fn drop(&mut self) {
+            assert_eq!(self as *mut Self as usize % 16, 0);
+            DROPPED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn swap_remove() {
+        let mut vec = BlobVec::new(Layout::new::<u32>(), None, 0);
+
+        for mut value in 0..4u32 {
+            unsafe { vec.push(&mut value as *mut u32 as *mut u8) };
+        }
+
+        let mut removed = 0u32;
+        unsafe { vec.swap_remove_unchecked(1, &mut removed as *mut u32 as *mut u8) };
+
+        assert_eq!(removed, 1);
+        assert_eq!(vec.len(), 3);
+
+        let values = (0..vec.len()).map(|i| unsafe { *(vec.get_unchecked(i) as *const u32) });
+        assert_eq!(values.collect::<Vec<_>>(), [0, 3, 2]);
+    }
+
+    #[test]
+    fn zero_sized() {
+        let drop_marker: unsafe fn(*mut u8) =
+            |ptr| unsafe { ptr::drop_in_place(ptr as *mut Marker) };
+        let mut vec = BlobVec::new(Layout::new::<Marker>(), Some(drop_marker), 0);
+
+        for _ in 0..100 {
+            let mut marker = Marker;
+            unsafe { vec.push(&mut marker as *mut Marker as *mut u8) };
+            std::mem::forget(marker);
+        }
+
+        assert_eq!(vec.len(), 100);
+        assert_eq!(vec.capacity(), usize::MAX);
+
+        for i in 0..10 {
+            unsafe { vec.swap_remove_and_drop_unchecked(i * 3) };
+        }
+
+        assert_eq!(vec.len(), 90);
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 10);
+
+        let mut removed = Marker;
+        unsafe { vec.swap_remove_unchecked(0, &mut removed as *mut Marker as *mut u8) };
+        assert_eq!(vec.len(), 89);
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 10);
+
+        for i in 0..vec.len() {
+            assert_eq!(unsafe { vec.get_unchecked(i) } as usize % 16, 0);
+        }
+
+        drop(vec);
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 99);
+
+        drop(removed);
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 100);
+    }
+}
@@ -89,6 +89,14 @@ impl Column {
         self.ticks.swap_remove(index);
     }
 
+    /// # Safety
+    /// - `a` and `b` must be in bounds
+    #[inline]
+    pub unsafe fn swap_unchecked(&mut self, a: usize, b: usize) {
+        unsafe { self.data.swap_unchecked(a, b) };
+        self.ticks.swap(a, b);
+    }
+
     #[inline]
     pub fn get_data(&self, index: usize) -> Option<*mut u8> {
         if index < self.len() {
@@ -10,7 +10,7 @@ use super::{Column, ComponentStorage, SparseArray};
 #[derive(Debug)]
 pub struct DenseStorage {
     dense: Column,
-    entities: Vec<u32>,
+    entities: Vec<Entity>,
     sparse: SparseArray<u32>,
 }
 
@@ -29,12 +29,59 @@ impl DenseStorage {
         self.dense.check_change_ticks(change_tick);
     }
 
+    /// Reorders the components by entity index, so that iterating entities in order walks the
+    /// column linearly.
+    ///
+    /// Removing components fills the gap with the last component, scrambling the order over
+    /// time, this undoes that. It's `O(n log n)` in the number of components, so it's best
+    /// called during a pause, see [`World::optimize_storage`].
+    ///
+    /// [`World::optimize_storage`]: crate::world::World::optimize_storage
+    pub fn sort_by_entity(&mut self) {
+        let mut sorted = self.entities.clone();
+        sorted.sort_unstable();
+
+        for (index, entity) in sorted.into_iter().enumerate() {
+            // SAFETY: every entity in `self.entities` is contained in `self.sparse`.
+            let current = unsafe { *self.sparse.get_unchecked(entity.index() as usize) } as usize;
+
+            if current != index {
+                // SAFETY: `index` and `current` are both less than `self.entities.len()`.
+                unsafe { self.swap_unchecked(index, current) };
+            }
+        }
+    }
+
+    /// Iterates over every entity in the storage, together with a pointer to its component.
+    ///
+    /// Entities are yielded in storage order, which is only ordered by entity index right
+    /// after [`DenseStorage::sort_by_entity`].
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, *mut u8)> + '_ {
+        let entities = self.entities.iter().enumerate();
+
+        // SAFETY: `self.entities` and `self.dense` have the same length.
+        entities.map(|(index, &entity)| (entity, unsafe { self.dense.get_data_unchecked(index) }))
+    }
+
+    /// # Safety
+    /// - `a` and `b` must be in bounds
+    #[inline]
+    unsafe fn swap_unchecked(&mut self, a: usize, b: usize) {
+        unsafe { self.dense.swap_unchecked(a, b) };
+        self.entities.swap(a, b);
+
+        let (entity_a, entity_b) = (self.entities[a], self.entities[b]);
+        unsafe { *self.sparse.get_unchecked_mut(entity_a.index() as usize) = a as u32 };
+        unsafe { *self.sparse.get_unchecked_mut(entity_b.index() as usize) = b as u32 };
+    }
+
     #[inline]
     unsafe fn swap(&mut self, index: usize) {
         self.entities.swap_remove(index);
         if index != self.dense.len() {
             let swapped = self.entities[index];
-            unsafe { *self.sparse.get_unchecked_mut(swapped as usize) = index as u32 };
+            unsafe { *self.sparse.get_unchecked_mut(swapped.index() as usize) = index as u32 };
         }
     }
 }
@@ -59,12 +106,13 @@ impl ComponentStorage for DenseStorage {
     unsafe fn insert(&mut self, entity: Entity, data: *mut u8, change_tick: u32) {
         if let Some(&index) = self.sparse.get(entity.index() as usize) {
             unsafe { self.dense.replace(index as usize, data, change_tick) };
+            self.entities[index as usize] = entity;
         } else {
             let dense_index = self.dense.len() as u32;
 
             unsafe { self.dense.push(data, ChangeTicks::new(change_tick)) };
             self.sparse.insert(entity.index() as usize, dense_index);
-            self.entities.push(entity.index());
+            self.entities.push(entity);
         }
     }
 
@@ -116,3 +164,35 @@ impl ComponentStorage for DenseStorage {
         (data, ticks)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        storage::ComponentStorage,
+        world::{ComponentDescriptor, Entity},
+    };
+
+    use super::DenseStorage;
+
+    #[test]
+    fn iter() {
+        let mut storage = DenseStorage::new(ComponentDescriptor::new::<i32>(), 0);
+
+        for index in 0..4 {
+            let entity = Entity::from_raw_parts(index, 1);
+            let mut value = index as i32 * 10;
+            unsafe { storage.insert(entity, &mut value as *mut i32 as *mut u8, 0) };
+        }
+
+        storage.remove_and_drop(Entity::from_raw_parts(1, 1));
+
+        let mut items = storage
+            .iter()
+            .map(|(entity, data)| (entity, unsafe { *(data as *const i32) }))
+            .collect::<Vec<_>>();
+        items.sort();
+
+        let expected = [0, 2, 3].map(|index| (Entity::from_raw_parts(index, 1), index as i32 * 10));
+        assert_eq!(items, expected);
+    }
+}
@@ -9,11 +9,15 @@ pub trait Resource: Send + Sync + 'static {}
 impl<T> Resource for T where T: Send + Sync + 'static {}
 
 impl dyn Resource {
+    /// # Safety
+    /// - `self` must be a `T`.
     #[inline]
     pub unsafe fn downcast_ref<T: Resource>(&self) -> &T {
         unsafe { &*(self as *const dyn Resource as *const T) }
     }
 
+    /// # Safety
+    /// - `self` must be a `T`.
     #[inline]
     pub unsafe fn downcast_mut<T: Resource>(&mut self) -> &mut T {
         unsafe { &mut *(self as *mut dyn Resource as *mut T) }
@@ -105,6 +109,8 @@ impl Resources {
         self.resources.contains(id.index())
     }
 
+    /// # Safety
+    /// - `resource` must be of the type registered with `id`.
     #[inline]
     pub unsafe fn insert(
         &mut self,
@@ -131,6 +137,13 @@ impl Resources {
         let data = self.resources.get(id.index())?;
         Some((data.as_ptr(), data.change_ticks.get()))
     }
+
+    #[inline]
+    pub fn check_change_ticks(&mut self, change_tick: u32) {
+        for (_, data) in self.resources.iter_mut() {
+            data.change_ticks_mut().check_ticks(change_tick);
+        }
+    }
 }
 
 impl std::fmt::Debug for Resources {
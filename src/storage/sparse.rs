@@ -1,3 +1,12 @@
+use std::{cell::UnsafeCell, collections::HashMap};
+
+use crate::{
+    change_detection::ChangeTicks,
+    world::{ComponentDescriptor, Entity, EntityIdSet},
+};
+
+use super::{Column, ComponentStorage};
+
 #[repr(transparent)]
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SparseArray<T> {
@@ -31,11 +40,15 @@ impl<T> SparseArray<T> {
         self.data.get_mut(index)?.as_mut()
     }
 
+    /// # Safety
+    /// - `self` must contain `index`.
     #[inline]
     pub unsafe fn get_unchecked(&self, index: usize) -> &T {
         unsafe { self.data.get_unchecked(index).as_ref().unwrap_unchecked() }
     }
 
+    /// # Safety
+    /// - `self` must contain `index`.
     #[inline]
     pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
         unsafe {
@@ -83,6 +96,8 @@ impl<T> SparseArray<T> {
         self.data.get_mut(index)?.take()
     }
 
+    /// # Safety
+    /// - `self` must contain `index`.
     #[inline]
     pub unsafe fn remove_unchecked(&mut self, index: usize) -> T {
         unsafe { self.data.get_unchecked_mut(index).take().unwrap_unchecked() }
@@ -109,3 +124,200 @@ impl<T> SparseArray<T> {
         self.data.clear()
     }
 }
+
+/// A component storage for components that are rarely present, or added and removed often.
+///
+/// Entities are mapped to their component through a hash map, so unlike [`DenseStorage`],
+/// memory is proportional to the number of stored components rather than the highest entity
+/// index, at the cost of slower lookups. Select it with
+/// `#[component(storage = "SparseSet")]`.
+///
+/// [`DenseStorage`]: super::DenseStorage
+#[derive(Debug)]
+pub struct SparseStorage {
+    dense: Column,
+    entities: Vec<Entity>,
+    indices: HashMap<u32, u32>,
+}
+
+impl SparseStorage {
+    #[inline]
+    pub fn new(desc: ComponentDescriptor, capacity: usize) -> Self {
+        Self {
+            dense: Column::with_capacity(&desc, capacity),
+            entities: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    #[inline]
+    pub fn check_change_ticks(&mut self, change_tick: u32) {
+        self.dense.check_change_ticks(change_tick);
+    }
+
+    /// Iterates over every entity in the storage, together with a pointer to its component.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, *mut u8)> + '_ {
+        let entities = self.entities.iter().enumerate();
+
+        // SAFETY: `self.entities` and `self.dense` have the same length.
+        entities.map(|(index, &entity)| (entity, unsafe { self.dense.get_data_unchecked(index) }))
+    }
+
+    #[inline]
+    fn index(&self, entity: Entity) -> Option<usize> {
+        self.indices
+            .get(&entity.index())
+            .map(|&index| index as usize)
+    }
+
+    /// # Safety
+    /// - `entity` must be contained in the storage.
+    #[inline]
+    unsafe fn index_unchecked(&self, entity: Entity) -> usize {
+        debug_assert!(self.indices.contains_key(&entity.index()));
+        unsafe { self.index(entity).unwrap_unchecked() }
+    }
+
+    /// Updates the index of the entity swapped into `index` by a swap remove.
+    #[inline]
+    fn swap(&mut self, index: usize) {
+        self.entities.swap_remove(index);
+
+        if let Some(swapped) = self.entities.get(index) {
+            self.indices.insert(swapped.index(), index as u32);
+        }
+    }
+}
+
+impl ComponentStorage for SparseStorage {
+    #[inline]
+    fn new(desc: ComponentDescriptor, capacity: usize) -> Self {
+        Self::new(desc, capacity)
+    }
+
+    #[inline]
+    fn contains(&self, entity: Entity) -> bool {
+        self.indices.contains_key(&entity.index())
+    }
+
+    #[inline]
+    fn entity_ids(&self) -> EntityIdSet {
+        self.entities
+            .iter()
+            .map(|entity| entity.index() as usize)
+            .collect()
+    }
+
+    #[inline]
+    unsafe fn insert(&mut self, entity: Entity, data: *mut u8, change_tick: u32) {
+        if let Some(index) = self.index(entity) {
+            unsafe { self.dense.replace(index, data, change_tick) };
+            self.entities[index] = entity;
+        } else {
+            let index = self.dense.len() as u32;
+
+            unsafe { self.dense.push(data, ChangeTicks::new(change_tick)) };
+            self.indices.insert(entity.index(), index);
+            self.entities.push(entity);
+        }
+    }
+
+    #[inline]
+    unsafe fn remove_unchecked(&mut self, entity: Entity, data: *mut u8) {
+        let index = unsafe { self.index_unchecked(entity) };
+        self.indices.remove(&entity.index());
+
+        unsafe { self.dense.swap_remove_unchecked(index, data) };
+        self.swap(index);
+    }
+
+    #[inline]
+    fn remove_and_drop(&mut self, entity: Entity) {
+        if let Some(index) = self.indices.remove(&entity.index()) {
+            // SAFETY: `index` is a valid index into `self.dense`.
+            unsafe { self.dense.swap_remove_and_drop_unchecked(index as usize) };
+            self.swap(index as usize);
+        }
+    }
+
+    #[inline]
+    unsafe fn get_unchecked(&self, entity: Entity) -> *mut u8 {
+        // SAFETY: `entity` is contained in self as per safety requirement.
+        let index = unsafe { self.index_unchecked(entity) };
+        unsafe { self.dense.get_data_unchecked(index) }
+    }
+
+    #[inline]
+    fn get(&self, entity: Entity) -> Option<*mut u8> {
+        self.dense.get_data(self.index(entity)?)
+    }
+
+    #[inline]
+    unsafe fn get_ticks_unchecked(&self, entity: Entity) -> &UnsafeCell<ChangeTicks> {
+        let index = unsafe { self.index_unchecked(entity) };
+        unsafe { self.dense.get_ticks_unchecked(index) }
+    }
+
+    #[inline]
+    unsafe fn get_with_ticks_unchecked(
+        &self,
+        entity: Entity,
+    ) -> (*mut u8, &UnsafeCell<ChangeTicks>) {
+        // SAFETY: `entity` is contained in self as per safety requirement.
+        let index = unsafe { self.index_unchecked(entity) };
+        let data = unsafe { self.dense.get_data_unchecked(index) };
+        let ticks = unsafe { self.dense.get_ticks_unchecked(index) };
+
+        (data, ticks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        storage::ComponentStorage,
+        world::{ComponentDescriptor, Entity},
+    };
+
+    use super::SparseStorage;
+
+    #[test]
+    fn insert_remove() {
+        let mut storage = SparseStorage::new(ComponentDescriptor::new::<i32>(), 0);
+
+        for index in [0, 1000, 7, 1_000_000] {
+            let entity = Entity::from_raw_parts(index, 1);
+            let mut value = index as i32;
+            unsafe { storage.insert(entity, &mut value as *mut i32 as *mut u8, 0) };
+        }
+
+        storage.remove_and_drop(Entity::from_raw_parts(1000, 1));
+
+        let mut removed = 0i32;
+        let entity = Entity::from_raw_parts(0, 1);
+        unsafe { storage.remove_unchecked(entity, &mut removed as *mut i32 as *mut u8) };
+        assert_eq!(removed, 0);
+
+        assert!(!storage.contains(Entity::from_raw_parts(1000, 1)));
+        assert_eq!(storage.len(), 2);
+
+        for index in [7, 1_000_000] {
+            let value = storage.get(Entity::from_raw_parts(index, 1)).unwrap();
+            assert_eq!(unsafe { *(value as *const i32) }, index as i32);
+        }
+
+        let entity_ids = storage.entity_ids();
+        assert_eq!(entity_ids.iter().collect::<Vec<_>>(), [7, 1_000_000]);
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     world::{ComponentDescriptor, ComponentId, ComponentInfo, Entity, EntityIdSet},
 };
 
-use super::{DenseStorage, Resources, SparseArray};
+use super::{DenseStorage, Resources, SparseArray, SparseStorage};
 
 #[derive(Debug)]
 pub struct StorageSet<T> {
@@ -35,11 +35,15 @@ where
         self.storage_sets.get_mut(id.index())
     }
 
+    /// # Safety
+    /// - A storage for `id` must have been initialized.
     #[inline]
     pub unsafe fn get_unchecked(&self, id: ComponentId) -> &T {
         unsafe { self.storage_sets.get_unchecked(id.index()) }
     }
 
+    /// # Safety
+    /// - A storage for `id` must have been initialized.
     #[inline]
     pub unsafe fn get_unchecked_mut(&mut self, id: ComponentId) -> &mut T {
         unsafe { self.storage_sets.get_unchecked_mut(id.index()) }
@@ -66,10 +70,14 @@ where
         }
     }
 
+    /// Removes and drops every component of `entity`, calling `removed` with the id of each.
     #[inline]
-    pub fn remove(&mut self, entity: Entity) {
-        for (_, storage) in self.storage_sets.iter_mut() {
-            storage.remove_and_drop(entity);
+    pub fn remove(&mut self, entity: Entity, mut removed: impl FnMut(ComponentId)) {
+        for (index, storage) in self.storage_sets.iter_mut() {
+            if storage.contains(entity) {
+                storage.remove_and_drop(entity);
+                removed(ComponentId::new(index));
+            }
         }
     }
 }
@@ -77,24 +85,45 @@ where
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum StorageType {
     Dense,
+    SparseSet,
     Resource,
 }
 
 #[derive(Debug, Default)]
 pub struct Storages {
     pub dense: StorageSet<DenseStorage>,
+    pub sparse: StorageSet<SparseStorage>,
     pub resources: Resources,
 }
 
 impl Storages {
+    /// Returns the storage of the component `id`, whichever storage type it uses.
+    #[inline]
+    pub fn get(&self, id: ComponentId) -> Option<&dyn ComponentStorage> {
+        if let Some(storage) = self.dense.get(id) {
+            return Some(storage);
+        }
+
+        Some(self.sparse.get(id)?)
+    }
+
+    /// Removes and drops every component of `entity`, calling `removed` with the id of each.
     #[inline]
-    pub fn remove(&mut self, entity: Entity) {
-        self.dense.remove(entity);
+    pub fn remove(&mut self, entity: Entity, mut removed: impl FnMut(ComponentId)) {
+        self.dense.remove(entity, &mut removed);
+        self.sparse.remove(entity, &mut removed);
+    }
+
+    /// Removes and drops the component `id` of `entity`, if it has it.
+    #[inline]
+    pub fn remove_and_drop(&mut self, entity: Entity, id: ComponentId) {
+        self.dense.remove_and_drop(entity, id);
+        self.sparse.remove_and_drop(entity, id);
     }
 
     #[inline]
     pub fn contains(&self, id: ComponentId, entity: Entity) -> bool {
-        if let Some(storage) = self.dense.get(id) {
+        if let Some(storage) = self.get(id) {
             return storage.contains(entity);
         }
 
@@ -103,24 +132,40 @@ impl Storages {
 
     #[inline]
     pub fn entity_ids(&self, id: ComponentId) -> EntityIdSet {
-        if let Some(sparse) = self.dense.get(id) {
-            return sparse.entity_ids();
+        if let Some(storage) = self.get(id) {
+            return storage.entity_ids();
         }
 
         EntityIdSet::default()
     }
 
+    /// Sorts every dense storage by entity index, see [`DenseStorage::sort_by_entity`].
+    #[inline]
+    pub fn sort_by_entity(&mut self) {
+        for (_, storage) in self.dense.storage_sets.iter_mut() {
+            storage.sort_by_entity();
+        }
+    }
+
     #[inline]
     pub fn check_change_ticks(&mut self, tick: u32) {
         for (_, storage) in self.dense.storage_sets.iter_mut() {
             storage.check_change_ticks(tick);
         }
+
+        for (_, storage) in self.sparse.storage_sets.iter_mut() {
+            storage.check_change_ticks(tick);
+        }
+
+        self.resources.check_change_ticks(tick);
     }
 }
 
 pub trait ComponentStorage: Send + Sync + 'static {
     /// Create a new storage for the given component type `T`.
-    fn new(desc: ComponentDescriptor, capacity: usize) -> Self;
+    fn new(desc: ComponentDescriptor, capacity: usize) -> Self
+    where
+        Self: Sized;
 
     /// Returns `true` if the storage contains a component for the given entity.
     fn contains(&self, entity: Entity) -> bool;
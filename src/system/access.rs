@@ -112,14 +112,14 @@ where
         conflicts
     }
 
+    /// Returns `true` if `self` and `other` can be accessed at the same time.
+    ///
+    /// An access that reads everything is incompatible with any write, regardless of which
+    /// side it's on, or whether that side also writes.
     #[inline]
     pub fn is_compatible(&self, other: &Self) -> bool {
-        if self.read_all {
-            return other.write.count_ones(..) == 0;
-        }
-
-        if other.read_all {
-            return self.write.count_ones(..) == 0;
+        if self.read_all && other.write_any() || other.read_all && self.write_any() {
+            return false;
         }
 
         self.write.is_disjoint(&other.read) && self.read.is_disjoint(&other.write)
@@ -300,3 +300,135 @@ where
         self.without.clear();
     }
 }
+
+/// The access of a whole system, keeping the [`FilteredAccess`] of each parameter separate.
+///
+/// Merging the `with` and `without` filters of different parameters into one
+/// [`FilteredAccess`] would make unrelated filters look disjoint, so compatibility is instead
+/// checked pairwise between the individual accesses.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FilteredAccessSet<T> {
+    combined: Access<T>,
+    filtered: Vec<FilteredAccess<T>>,
+}
+
+impl<T> Default for FilteredAccessSet<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            combined: Access::default(),
+            filtered: Vec::new(),
+        }
+    }
+}
+
+impl<T> FilteredAccessSet<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn grow(&mut self, new_capacity: usize) {
+        self.combined.grow(new_capacity);
+    }
+}
+
+impl<T> FilteredAccessSet<T>
+where
+    T: Into<usize> + From<usize> + Copy,
+{
+    /// Returns the union of every access in the set, ignoring filters.
+    #[inline]
+    pub fn combined(&self) -> &Access<T> {
+        &self.combined
+    }
+
+    #[inline]
+    pub fn iter_filtered(&self) -> impl Iterator<Item = &FilteredAccess<T>> + '_ {
+        self.filtered.iter()
+    }
+
+    /// Adds `access` to the set, e.g. the access of a [`Query`](crate::query::Query).
+    #[inline]
+    pub fn add(&mut self, access: FilteredAccess<T>) {
+        self.combined.extend(access.access());
+        self.filtered.push(access);
+    }
+
+    /// Adds a read of `index` that isn't restricted by any filter, e.g. a resource.
+    #[inline]
+    pub fn add_read(&mut self, index: T) {
+        let mut access = FilteredAccess::new();
+        access.access_mut().add_read(index);
+        self.add(access);
+    }
+
+    /// Adds a write of `index` that isn't restricted by any filter, e.g. a resource.
+    #[inline]
+    pub fn add_write(&mut self, index: T) {
+        let mut access = FilteredAccess::new();
+        access.access_mut().add_write(index);
+        self.add(access);
+    }
+
+    #[inline]
+    pub fn read_all(&mut self) {
+        let mut access = FilteredAccess::new();
+        access.read_all();
+        self.add(access);
+    }
+
+    #[inline]
+    pub fn has_read(&self, index: T) -> bool {
+        self.combined.has_read(index)
+    }
+
+    #[inline]
+    pub fn has_write(&self, index: T) -> bool {
+        self.combined.has_write(index)
+    }
+
+    #[inline]
+    pub fn write_any(&self) -> bool {
+        self.combined.write_any()
+    }
+
+    #[inline]
+    pub fn extend(&mut self, other: &Self) {
+        self.combined.extend(&other.combined);
+        self.filtered.extend_from_slice(&other.filtered);
+    }
+
+    /// Returns the components that `access` conflicts with.
+    #[inline]
+    pub fn get_conflicts(&self, access: &FilteredAccess<T>) -> Vec<T> {
+        let mut conflicts = FixedBitSet::with_capacity(0);
+
+        if !self.combined.is_compatible(access.access()) {
+            for filtered in self.filtered.iter() {
+                let filtered_conflicts = filtered.get_conflicts(access);
+                conflicts.extend(filtered_conflicts.into_iter().map(Into::into));
+            }
+        }
+
+        conflicts.ones().map(T::from).collect()
+    }
+
+    #[inline]
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        if self.combined.is_compatible(&other.combined) {
+            return true;
+        }
+
+        self.filtered.iter().all(|filtered| {
+            (other.filtered.iter()).all(|other_filtered| filtered.is_compatible(other_filtered))
+        })
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.combined.clear();
+        self.filtered.clear();
+    }
+}
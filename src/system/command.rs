@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{fmt::Display, marker::PhantomData};
 
 use crate::{
     bundle::Bundle,
@@ -12,18 +12,136 @@ pub struct CommandQueue {
 }
 
 impl CommandQueue {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns the number of commands the queue can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+
+    /// Drops all queued commands without applying them, keeping the allocated capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Applies all queued commands to `world`.
+    ///
+    /// Commands that fail don't stop the rest of the queue from being applied, their errors are
+    /// collected in the [`CommandErrors`] resource instead. The queue keeps its capacity, so a
+    /// queue that is reused, like the one owned by a system's [`Commands`], stops reallocating
+    /// once it has grown to fit the commands queued each run.
     #[inline]
     pub fn apply(&mut self, world: &mut World) {
         world.flush();
 
         for command in self.queue.drain(..) {
-            command.apply(world);
+            if let Err(error) = command.apply(world) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("failed to apply command: {}", error);
+
+                world.resource_or_default::<CommandErrors>().push(error);
+            }
         }
     }
 }
 
+/// An error returned by a [`Command`] that failed to apply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandError {
+    /// The entity the command targets doesn't exist.
+    NoSuchEntity(Entity),
+}
+
+impl Display for CommandError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSuchEntity(entity) => write!(f, "entity {} does not exist", entity),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// A resource collecting the errors of commands that failed to apply.
+///
+/// Errors are double buffered like [`Events`](crate::event::Events), they're kept until the
+/// second [`World::clear_trackers`] after they happened, so the resource doesn't grow without
+/// bound when nothing drains it.
+#[derive(Clone, Debug, Default)]
+pub struct CommandErrors {
+    errors: Vec<CommandError>,
+    /// The number of errors at the front of `errors` pushed before the last update.
+    older: usize,
+}
+
+impl CommandErrors {
+    #[inline]
+    pub fn push(&mut self, error: CommandError) {
+        self.errors.push(error);
+    }
+
+    /// Drops the errors pushed before the last update, called by [`World::clear_trackers`].
+    #[inline]
+    pub fn update(&mut self) {
+        self.errors.drain(..self.older);
+        self.older = self.errors.len();
+    }
+
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, CommandError> {
+        self.errors.iter()
+    }
+
+    #[inline]
+    pub fn drain(&mut self) -> std::vec::Drain<'_, CommandError> {
+        self.older = 0;
+        self.errors.drain(..)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.older = 0;
+        self.errors.clear();
+    }
+}
+
+impl World {
+    /// Runs `f` with [`Commands`] for `self`, then applies the queued commands.
+    ///
+    /// This is useful for using [`Commands`] outside of systems.
+    #[inline]
+    pub fn with_commands<T>(&mut self, f: impl FnOnce(&mut Commands<'_, '_>) -> T) -> T {
+        let mut queue = CommandQueue::default();
+        let out = f(&mut Commands::new(&mut queue, self));
+        queue.apply(self);
+        out
+    }
+}
+
 pub trait Command: Send + Sync + 'static {
-    fn apply(self: Box<Self>, world: &mut World);
+    /// Applies the command to `world`, infallible commands always return `Ok(())`.
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError>;
 
     #[inline]
     fn name(&self) -> &str {
@@ -55,18 +173,93 @@ impl<'w, 's> Commands<'w, 's> {
         self.world
     }
 
+    /// Returns the number of commands queued and not yet applied.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns `true` if more than `threshold` commands are queued.
+    ///
+    /// With the `tracing` feature a warning is logged as well, which helps catch systems that
+    /// queue commands without bound during development.
+    #[inline]
+    pub fn warn_if_over(&self, threshold: usize) -> bool {
+        let over = self.len() > threshold;
+
+        #[cfg(feature = "tracing")]
+        if over {
+            tracing::warn!(
+                "{} commands queued, more than the threshold of {}",
+                self.len(),
+                threshold,
+            );
+        }
+
+        over
+    }
+
     #[inline]
     pub fn add_command(&mut self, command: impl Command) -> &mut Self {
         self.queue.queue.push(Box::new(command));
         self
     }
 
+    /// Queues `f` to be run with the world when the commands are applied.
+    ///
+    /// ```
+    /// # use shiv::prelude::*;
+    /// # struct Score(u32);
+    /// fn reset_system(mut commands: Commands) {
+    ///     commands.add_fn(|world| world.insert_resource(Score(0)));
+    /// }
+    /// ```
+    #[inline]
+    pub fn add_fn(&mut self, f: impl FnOnce(&mut World) + Send + Sync + 'static) -> &mut Self {
+        self.add_command(FnCommand(f))
+    }
+
     #[inline]
     pub fn spawn<'a>(&'a mut self) -> EntityCommands<'w, 's, 'a> {
         let entity = self.world.reserve_entity();
         EntityCommands::new(self, entity)
     }
 
+    /// Spawns an entity with `bundle` inserted when the commands are applied.
+    #[inline]
+    pub fn spawn_with<'a, T: Bundle>(&'a mut self, bundle: T) -> EntityCommands<'w, 's, 'a> {
+        let mut entity = self.spawn();
+        entity.insert(bundle);
+        entity
+    }
+
+    /// Spawns an entity for every bundle in `iter` when the commands are applied, see
+    /// [`World::spawn_batch`].
+    #[inline]
+    pub fn spawn_batch<I>(&mut self, iter: I)
+    where
+        I: IntoIterator + Send + Sync + 'static,
+        I::Item: Bundle,
+    {
+        self.add_command(SpawnBatch { iter });
+    }
+
+    /// Inserts every bundle in `iter` into its entity when the commands are applied, see
+    /// [`World::insert_or_spawn_batch`].
+    #[inline]
+    pub fn insert_or_spawn_batch<I, B>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (Entity, B)> + Send + Sync + 'static,
+        B: Bundle,
+    {
+        self.add_command(InsertOrSpawnBatch { iter });
+    }
+
     #[inline]
     pub fn get_or_spawn<'a>(&'a mut self, entity: Entity) -> EntityCommands<'w, 's, 'a> {
         self.add_command(GetOrSpawn { entity });
@@ -160,6 +353,17 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self
     }
 
+    /// Removes every component that isn't in the bundle `T`.
+    #[inline]
+    pub fn retain<T: Bundle>(&mut self) -> &mut Self {
+        self.commands.add_command(Retain {
+            entity: self.entity,
+            marker: PhantomData::<T>,
+        });
+
+        self
+    }
+
     #[inline]
     pub fn despawn(&mut self) {
         self.commands.add_command(Despawn {
@@ -168,6 +372,20 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     }
 }
 
+/// A [`Command`] running a closure, see [`Commands::add_fn`].
+pub struct FnCommand<F>(pub F);
+
+impl<F> Command for FnCommand<F>
+where
+    F: FnOnce(&mut World) + Send + Sync + 'static,
+{
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        (self.0)(world);
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct Insert<T> {
     pub entity: Entity,
@@ -175,8 +393,12 @@ pub struct Insert<T> {
 }
 
 impl<T: Bundle> Command for Insert<T> {
-    fn apply(self: Box<Self>, world: &mut World) {
-        world.entity_mut(self.entity).insert(self.bundle);
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        let mut entity =
+            (world.get_entity_mut(self.entity)).ok_or(CommandError::NoSuchEntity(self.entity))?;
+        entity.insert(self.bundle);
+
+        Ok(())
     }
 }
 
@@ -187,8 +409,28 @@ pub struct Remove<T> {
 }
 
 impl<T: Bundle> Command for Remove<T> {
-    fn apply(self: Box<Self>, world: &mut World) {
-        world.entity_mut(self.entity).remove::<T>();
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        let mut entity =
+            (world.get_entity_mut(self.entity)).ok_or(CommandError::NoSuchEntity(self.entity))?;
+        entity.remove::<T>();
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct Retain<T> {
+    pub entity: Entity,
+    pub marker: PhantomData<T>,
+}
+
+impl<T: Bundle> Command for Retain<T> {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        let mut entity =
+            (world.get_entity_mut(self.entity)).ok_or(CommandError::NoSuchEntity(self.entity))?;
+        entity.retain::<T>();
+
+        Ok(())
     }
 }
 
@@ -198,8 +440,12 @@ pub struct Despawn {
 }
 
 impl Command for Despawn {
-    fn apply(self: Box<Self>, world: &mut World) {
-        world.despawn(self.entity);
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        if world.despawn(self.entity) {
+            Ok(())
+        } else {
+            Err(CommandError::NoSuchEntity(self.entity))
+        }
     }
 }
 
@@ -209,8 +455,44 @@ pub struct GetOrSpawn {
 }
 
 impl Command for GetOrSpawn {
-    fn apply(self: Box<Self>, world: &mut World) {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
         world.get_or_spawn(self.entity);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct SpawnBatch<I> {
+    pub iter: I,
+}
+
+impl<I> Command for SpawnBatch<I>
+where
+    I: IntoIterator + Send + Sync + 'static,
+    I::Item: Bundle,
+{
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        world.spawn_batch(self.iter);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct InsertOrSpawnBatch<I> {
+    pub iter: I,
+}
+
+impl<I, B> Command for InsertOrSpawnBatch<I>
+where
+    I: IntoIterator<Item = (Entity, B)> + Send + Sync + 'static,
+    B: Bundle,
+{
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
+        world.insert_or_spawn_batch(self.iter);
+
+        Ok(())
     }
 }
 
@@ -220,8 +502,10 @@ pub struct InsertResource<T> {
 }
 
 impl<T: Resource> Command for InsertResource<T> {
-    fn apply(self: Box<Self>, world: &mut World) {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
         world.insert_resource(self.resource);
+
+        Ok(())
     }
 }
 
@@ -231,8 +515,10 @@ pub struct RemoveResource<T> {
 }
 
 impl<T: Resource> Command for RemoveResource<T> {
-    fn apply(self: Box<Self>, world: &mut World) {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
         world.remove_resource::<T>();
+
+        Ok(())
     }
 }
 
@@ -242,7 +528,164 @@ pub struct InitResource<T> {
 }
 
 impl<T: Resource + FromWorld> Command for InitResource<T> {
-    fn apply(self: Box<Self>, world: &mut World) {
+    fn apply(self: Box<Self>, world: &mut World) -> Result<(), CommandError> {
         world.init_resource::<T>();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        system::{CommandErrors, CommandQueue, Commands},
+        world::World,
+    };
+
+    use super::CommandError;
+
+    #[test]
+    fn failing_command() {
+        let mut world = World::new();
+        let despawned = world.spawn().entity();
+        let entity = world.spawn().entity();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(despawned).insert(1i32);
+        commands.entity(entity).insert(2i32);
+        world.despawn(despawned);
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<i32>(entity), Some(&2));
+
+        let errors = world.resource::<CommandErrors>();
+        let errors = errors.iter().copied().collect::<Vec<_>>();
+        assert_eq!(errors, vec![CommandError::NoSuchEntity(despawned)]);
+
+        // errors are dropped after two updates
+        world.clear_trackers();
+        assert_eq!(world.resource::<CommandErrors>().len(), 1);
+        world.clear_trackers();
+        assert!(world.resource::<CommandErrors>().is_empty());
+    }
+
+    #[test]
+    fn reuse_capacity() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+
+        let mut capacity = 0;
+        for frame in 0..4 {
+            let mut commands = Commands::new(&mut queue, &world);
+            for i in 0..100 {
+                commands.spawn().insert(i);
+            }
+
+            assert_eq!(queue.len(), 100);
+            if frame > 0 {
+                assert_eq!(queue.capacity(), capacity);
+            }
+
+            queue.apply(&mut world);
+            assert!(queue.is_empty());
+
+            capacity = queue.capacity();
+        }
+
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.insert_resource(0u32);
+        queue.clear();
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.capacity(), capacity);
+
+        queue.apply(&mut world);
+        assert!(!world.contains_resource::<u32>());
+    }
+
+    #[test]
+    fn commands_len() {
+        let world = World::new();
+        let mut queue = CommandQueue::default();
+
+        let mut commands = Commands::new(&mut queue, &world);
+        assert!(commands.is_empty());
+
+        for i in 0..10 {
+            commands.spawn().insert(i);
+        }
+
+        assert_eq!(commands.len(), 10);
+        assert!(!commands.warn_if_over(10));
+        assert!(commands.warn_if_over(9));
+    }
+
+    #[test]
+    fn spawn_batch() {
+        let mut world = World::new();
+        let existing = world.spawn().insert(0i32).entity();
+
+        world.with_commands(|commands| {
+            commands.spawn_batch((1..4).map(|i| (i, i as f32)));
+            commands.insert_or_spawn_batch(vec![(existing, 10i32)]);
+        });
+
+        let query = world.query::<(&i32, Option<&f32>)>();
+        let items = query.iter(&world).collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                (&10, None),
+                (&1, Some(&1.0)),
+                (&2, Some(&2.0)),
+                (&3, Some(&3.0))
+            ]
+        );
+    }
+
+    #[test]
+    fn with_commands() {
+        let mut world = World::new();
+
+        let entity = world.with_commands(|commands| {
+            commands.insert_resource(3u32);
+            commands.spawn().insert(2i32).entity()
+        });
+
+        assert_eq!(world.get::<i32>(entity), Some(&2));
+        assert_eq!(*world.resource::<u32>(), 3);
+    }
+
+    #[test]
+    fn add_fn() {
+        let mut world = World::new();
+        let entity = world.spawn().insert(1i32).entity();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.add_fn(move |world| {
+            *world.get_mut::<i32>(entity).unwrap() += 1;
+            world.insert_resource(3u32);
+        });
+
+        assert_eq!(world.get::<i32>(entity), Some(&1));
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<i32>(entity), Some(&2));
+        assert_eq!(*world.resource::<u32>(), 3);
+    }
+
+    #[test]
+    fn spawn_with() {
+        let mut world = World::new();
+        let a = world.spawn_with((1i32, true)).entity();
+
+        let b = world.with_commands(|commands| commands.spawn_with((2i32, false)).entity());
+
+        assert_eq!(world.get::<i32>(a), Some(&1));
+        assert_eq!(world.get::<bool>(a), Some(&true));
+        assert_eq!(world.get::<i32>(b), Some(&2));
+        assert_eq!(world.get::<bool>(b), Some(&false));
     }
 }
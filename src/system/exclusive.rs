@@ -101,6 +101,7 @@ where
     fn init(&mut self, world: &mut World) {
         self.store_last_change_tick();
         self.meta.last_change_tick = self.get_last_change_tick(world);
+        self.meta.grow_access(world);
 
         self.param_state = Some(<Param::Fetch as ExclusiveSystemParamState>::init(
             world,
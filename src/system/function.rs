@@ -113,6 +113,7 @@ where
         self.meta.last_change_tick = self.get_last_change_tick(world);
 
         self.meta.access.clear();
+        self.meta.grow_access(world);
         self.param_state = Some(<Param::Fetch as SystemParamState>::init(
             world,
             &mut self.meta,
@@ -4,6 +4,7 @@ mod exclusive;
 mod exclusive_param;
 mod function;
 mod param;
+mod removed_components;
 mod system;
 mod system_piping;
 
@@ -13,15 +14,16 @@ pub use exclusive::*;
 pub use exclusive_param::*;
 pub use function::*;
 pub use param::*;
+pub use removed_components::*;
 pub use system::*;
 pub use system_piping::*;
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        query::Query,
-        system::{IntoSystem, Res, ResMut, System},
-        world::World,
+        query::{Query, With, Without},
+        system::{IntoSystem, ParamSet, RemovedComponents, Res, ResMut, Single, System},
+        world::{ComponentId, Entities, Entity, World},
     };
 
     #[test]
@@ -45,4 +47,255 @@ mod tests {
         let mut system = system.into_system();
         system.init(&mut world);
     }
+
+    #[test]
+    fn disjoint_filtered_access() {
+        fn with_bool(_query: Query<&mut i32, With<bool>>) {}
+        fn without_bool(_query: Query<&mut i32, Without<bool>>) {}
+
+        let mut world = World::new();
+
+        let mut a = with_bool.into_system();
+        let mut b = without_bool.into_system();
+        a.init(&mut world);
+        b.init(&mut world);
+
+        assert!(a.meta().access.is_compatible(&b.meta().access));
+    }
+
+    #[test]
+    fn merged_filters_conflict() {
+        // `_b` alone doesn't make `_a` disjoint from `Without<bool>`
+        fn unfiltered(_a: Query<&mut i32>, _b: Query<&bool>) {}
+        fn without_bool(_query: Query<&mut i32, Without<bool>>) {}
+
+        let mut world = World::new();
+
+        let mut a = unfiltered.into_system();
+        let mut b = without_bool.into_system();
+        a.init(&mut world);
+        b.init(&mut world);
+
+        assert!(!a.meta().access.is_compatible(&b.meta().access));
+    }
+
+    #[test]
+    fn entities_param() {
+        fn system(entities: &Entities, mut alive: ResMut<Vec<bool>>, targets: Res<Vec<Entity>>) {
+            *alive = targets.iter().map(|&e| entities.contains(e)).collect();
+        }
+
+        let mut world = World::new();
+        let a = world.spawn().entity();
+        let b = world.spawn().entity();
+        world.despawn(a);
+
+        world.insert_resource(vec![a, b]);
+        world.insert_resource(Vec::<bool>::new());
+
+        let mut system = system.into_system();
+        system.init(&mut world);
+        system.run((), &mut world);
+
+        assert_eq!(*world.resource::<Vec<bool>>(), vec![false, true]);
+    }
+
+    #[test]
+    fn res_cloned() {
+        fn snapshot_system(scores: ResMut<Vec<u32>>, mut snapshots: ResMut<Vec<(Vec<u32>, bool)>>) {
+            snapshots.push((scores.cloned(), scores.is_changed()));
+        }
+
+        let mut world = World::new();
+        world.insert_resource(vec![1u32, 2, 3]);
+        world.insert_resource(Vec::<(Vec<u32>, bool)>::new());
+
+        let mut system = snapshot_system.into_system();
+        system.init(&mut world);
+        system.run((), &mut world);
+        system.run((), &mut world);
+
+        let snapshots = world.resource::<Vec<(Vec<u32>, bool)>>();
+        assert_eq!(
+            *snapshots,
+            vec![(vec![1, 2, 3], true), (vec![1, 2, 3], false)]
+        );
+    }
+
+    #[test]
+    fn res_mut_projection() {
+        struct Config {
+            scale: f32,
+            flags: Option<u32>,
+        }
+
+        fn helper(config: &mut ResMut<Config>) {
+            config.scale *= 2.0;
+        }
+
+        fn system(mut config: ResMut<Config>) {
+            helper(&mut config.reborrow());
+
+            let flags = config.filter_map_unchanged(|config| config.flags.as_mut());
+            assert!(flags.is_some());
+        }
+
+        fn check_system(config: Res<Config>) {
+            assert!(config.is_changed());
+            assert_eq!(config.scale, 2.0);
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Config {
+            scale: 1.0,
+            flags: Some(0),
+        });
+
+        let mut system = system.into_system();
+        system.init(&mut world);
+        system.run((), &mut world);
+
+        let mut check_system = check_system.into_system();
+        check_system.init(&mut world);
+        check_system.run((), &mut world);
+    }
+
+    #[test]
+    fn res_component_id() {
+        fn system(value: Res<i32>, mut ids: ResMut<Vec<ComponentId>>) {
+            ids.push(value.component_id());
+
+            let id = ids.component_id();
+            ids.push(id);
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0i32);
+        world.insert_resource(Vec::<ComponentId>::new());
+
+        let mut system = system.into_system();
+        system.init(&mut world);
+        system.run((), &mut world);
+
+        let components = world.components();
+        let expected = vec![
+            components.get_resource::<i32>().unwrap(),
+            components.get_resource::<Vec<ComponentId>>().unwrap(),
+        ];
+        assert_eq!(*world.resource::<Vec<ComponentId>>(), expected);
+    }
+
+    #[test]
+    fn single_param() {
+        fn read_single(value: Single<&i32, With<bool>>, mut out: ResMut<u32>) {
+            *out = **value as u32;
+        }
+
+        fn write_single(mut value: Single<&mut i32, Without<bool>>) {
+            **value += 1;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.spawn().insert(1i32).insert(true);
+        let entity = world.spawn().insert(2i32).entity();
+
+        let mut read_single = read_single.into_system();
+        read_single.init(&mut world);
+        read_single.run((), &mut world);
+        assert_eq!(*world.resource::<u32>(), 1);
+
+        let mut write_single = write_single.into_system();
+        write_single.init(&mut world);
+        write_single.run((), &mut world);
+        assert_eq!(world.get::<i32>(entity), Some(&3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn single_param_multiple() {
+        fn system(_value: Single<&i32>) {}
+
+        let mut world = World::new();
+        world.spawn().insert(1i32);
+        world.spawn().insert(2i32);
+
+        let mut system = system.into_system();
+        system.init(&mut world);
+        system.run((), &mut world);
+    }
+
+    #[test]
+    fn param_set() {
+        fn system(
+            mut set: ParamSet<(Query<&mut i32>, Query<&i32, With<bool>>)>,
+            mut out: ResMut<u32>,
+        ) {
+            for mut value in set.p0().iter_mut() {
+                *value += 1;
+            }
+
+            *out = set.p1().iter().map(|&value| value as u32).sum();
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.spawn().insert(1i32).insert(true);
+        world.spawn().insert(2i32);
+
+        let mut system = system.into_system();
+        system.init(&mut world);
+        system.run((), &mut world);
+        assert_eq!(*world.resource::<u32>(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn param_set_conflicts_outside() {
+        fn system(_set: ParamSet<(Query<&mut i32>, Query<&i32>)>, _query: Query<&i32>) {}
+
+        let mut world = World::new();
+
+        let mut system = system.into_system();
+        system.init(&mut world);
+    }
+
+    #[test]
+    fn removed_components() {
+        fn collect(mut removed: RemovedComponents<i32>, mut out: ResMut<Vec<Entity>>) {
+            out.extend(removed.iter());
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<Entity>::new());
+
+        let a = world.spawn().insert(1i32).entity();
+        let b = world.spawn().insert(2i32).insert(true).entity();
+        let c = world.spawn().insert(3i32).entity();
+
+        let mut system = collect.into_system();
+        system.init(&mut world);
+
+        world.remove::<i32>(a);
+        world.despawn(b);
+        world.remove::<bool>(c);
+        world.despawn(b);
+        system.run((), &mut world);
+        assert_eq!(*world.resource::<Vec<Entity>>(), vec![a, b]);
+
+        // removals are only seen once
+        world.clear_trackers();
+        world.despawn(c);
+        system.run((), &mut world);
+        assert_eq!(*world.resource::<Vec<Entity>>(), vec![a, b, c]);
+
+        // and dropped after two updates
+        world.clear_trackers();
+        world.clear_trackers();
+        world.resource_mut::<Vec<Entity>>().clear();
+        let mut late = collect.into_system();
+        late.init(&mut world);
+        late.run((), &mut world);
+        assert!(world.resource::<Vec<Entity>>().is_empty());
+    }
 }
@@ -6,9 +6,9 @@ use std::{
 
 use crate::{
     change_detection::ChangeTicks,
-    query::{Query, QueryState, ReadOnlyWorldQuery, WorldQuery},
+    query::{Query, QueryItem, QuerySingleError, QueryState, ReadOnlyWorldQuery, WorldQuery},
     storage::Resource,
-    world::{ComponentId, FromWorld, World},
+    world::{ComponentId, Entities, FromWorld, World},
 };
 
 use super::{CommandQueue, Commands, FilteredAccess, SystemMeta};
@@ -18,6 +18,8 @@ use super::{CommandQueue, Commands, FilteredAccess, SystemMeta};
 /// For more information, see [`SystemParam`].
 pub use shiv_macro::SystemParam;
 
+/// # Safety
+/// - Implementors must only access the world immutably.
 pub unsafe trait ReadOnlySystemParamFetch: for<'w, 's> SystemParamFetch<'w, 's> {}
 
 /// A trait that allows a type to be used as a parameter to a system.
@@ -41,6 +43,9 @@ pub trait SystemParam: Sized {
 /// The item fetched by a [`SystemParam`].
 pub type SystemParamItem<'w, 's, P> = <<P as SystemParam>::Fetch as SystemParamFetch<'w, 's>>::Item;
 
+/// # Safety
+/// - [`SystemParamState::init`] must register every component and resource accessed by the
+///   parameter in `meta`, and panic if that access conflicts with the rest of the system.
 pub unsafe trait SystemParamState: Send + Sync + 'static {
     fn init(world: &mut World, meta: &mut SystemMeta) -> Self;
 
@@ -51,6 +56,10 @@ pub unsafe trait SystemParamState: Send + Sync + 'static {
 pub trait SystemParamFetch<'w, 's>: SystemParamState {
     type Item: SystemParam<Fetch = Self>;
 
+    /// # Safety
+    /// - `world` must be the world `self` was initialized with.
+    /// - This function does not check borrow rules, so it's up to the caller to ensure that access
+    ///   is valid.
     unsafe fn get_param(
         &'s mut self,
         meta: &SystemMeta,
@@ -92,6 +101,36 @@ impl<'w, 's> SystemParamFetch<'w, 's> for WorldFetch {
     }
 }
 
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct EntitiesFetch;
+
+impl SystemParam for &Entities {
+    type Fetch = EntitiesFetch;
+}
+
+// entities are only allocated and freed with `&mut World`, so reading them never conflicts
+unsafe impl SystemParamState for EntitiesFetch {
+    fn init(_world: &mut World, _meta: &mut SystemMeta) -> Self {
+        EntitiesFetch
+    }
+}
+
+impl<'w, 's> SystemParamFetch<'w, 's> for EntitiesFetch {
+    type Item = &'w Entities;
+
+    unsafe fn get_param(
+        &'s mut self,
+        _meta: &SystemMeta,
+        world: &'w World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        world.entities()
+    }
+}
+
+unsafe impl ReadOnlySystemParamFetch for EntitiesFetch {}
+
 unsafe impl SystemParamState for CommandQueue {
     fn init(_world: &mut World, _meta: &mut SystemMeta) -> Self {
         Self::default()
@@ -138,7 +177,7 @@ where
             world,
         );
 
-        meta.access.extend(&state.filtered_access);
+        meta.access.add(state.filtered_access.clone());
 
         state
     }
@@ -176,6 +215,257 @@ where
 {
 }
 
+/// A [`SystemParam`] fetching the only item matching the query `Q` with the filter `F`.
+///
+/// This is shorthand for a [`Query`] that is expected to always match exactly one entity,
+/// e.g. the main camera.
+///
+/// # Panics
+/// - When the system is run, if no entity or more than one entity matches the query.
+///
+/// # Example
+/// ```rust
+/// # use shiv::{prelude::*, query::With, system::Single};
+/// # #[derive(Component)]
+/// # struct Camera;
+/// # #[derive(Component)]
+/// # struct Position(f32);
+/// fn camera_system(position: Single<&Position, With<Camera>>) {
+///     println!("camera at {}", position.0);
+/// }
+/// ```
+pub struct Single<'w, Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static = ()> {
+    item: QueryItem<'w, Q>,
+    marker: PhantomData<F>,
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> Single<'w, Q, F> {
+    #[inline]
+    pub fn into_inner(self) -> QueryItem<'w, Q> {
+        self.item
+    }
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> Deref for Single<'w, Q, F> {
+    type Target = QueryItem<'w, Q>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> DerefMut for Single<'w, Q, F> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
+#[doc(hidden)]
+pub struct SingleState<Q: WorldQuery, F: ReadOnlyWorldQuery> {
+    state: QueryState<Q, F>,
+}
+
+unsafe impl<Q, F> SystemParamState for SingleState<Q, F>
+where
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    #[inline]
+    fn init(world: &mut World, meta: &mut SystemMeta) -> Self {
+        Self {
+            state: <QueryState<Q, F> as SystemParamState>::init(world, meta),
+        }
+    }
+}
+
+impl<'w, 's, Q, F> SystemParamFetch<'w, 's> for SingleState<Q, F>
+where
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    type Item = Single<'w, Q, F>;
+
+    #[track_caller]
+    unsafe fn get_param(
+        &'s mut self,
+        meta: &SystemMeta,
+        world: &'w World,
+        change_tick: u32,
+    ) -> Self::Item {
+        let single = unsafe {
+            (self.state).get_single_unchecked_manual(world, meta.last_change_tick, change_tick)
+        };
+
+        let item = match single {
+            Ok(item) => item,
+            Err(QuerySingleError::NoEntities) => panic!(
+                "Single<{}, {}> in system {} didn't match any entities.",
+                std::any::type_name::<Q>(),
+                std::any::type_name::<F>(),
+                meta.name(),
+            ),
+            Err(QuerySingleError::MultipleEntities) => panic!(
+                "Single<{}, {}> in system {} matched more than one entity.",
+                std::any::type_name::<Q>(),
+                std::any::type_name::<F>(),
+                meta.name(),
+            ),
+        };
+
+        Single {
+            item,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'w, Q, F> SystemParam for Single<'w, Q, F>
+where
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    type Fetch = SingleState<Q, F>;
+}
+
+unsafe impl<Q, F> ReadOnlySystemParamFetch for SingleState<Q, F>
+where
+    Q: ReadOnlyWorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+}
+
+/// A set of [`SystemParam`]s that may conflict with each other, e.g. two [`Query`]s accessing
+/// the same component mutably.
+///
+/// Members are only accessible one at a time through [`ParamSet::p0`], [`ParamSet::p1`], etc.
+/// which borrow the set mutably. Members are still checked for conflicts against parameters
+/// outside the set. Sets of up to 8 members are supported.
+///
+/// # Example
+/// ```rust
+/// # use shiv::{prelude::*, system::ParamSet};
+/// # #[derive(Component)]
+/// # struct Position(f32);
+/// # #[derive(Component)]
+/// # struct Player;
+/// fn follow_player(
+///     mut set: ParamSet<(Query<&mut Position>, Query<&Position, With<Player>>)>,
+/// ) {
+///     let target = set.p1().iter().map(|position| position.0).next().unwrap_or(0.0);
+///
+///     for mut position in set.p0().iter_mut() {
+///         position.0 = target;
+///     }
+/// }
+/// ```
+pub struct ParamSet<'w, 's, T: SystemParam> {
+    state: &'s mut T::Fetch,
+    meta: &'s SystemMeta,
+    world: &'w World,
+    change_tick: u32,
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct ParamSetState<T> {
+    state: T,
+    meta: SystemMeta,
+}
+
+macro_rules! impl_param_set {
+    (@ $(($param:ident, $fn:ident, $index:tt))*) => {
+        impl<'w, 's, $($param: SystemParam),*> ParamSet<'w, 's, ($($param,)*)> {
+            $(
+                /// Gets the member of the set at this position, borrowing the whole set.
+                #[inline]
+                pub fn $fn(&mut self) -> SystemParamItem<'_, '_, $param> {
+                    unsafe { self.state.$index.get_param(self.meta, self.world, self.change_tick) }
+                }
+            )*
+        }
+
+        impl<'w, 's, $($param: SystemParam),*> SystemParam for ParamSet<'w, 's, ($($param,)*)> {
+            type Fetch = ParamSetState<($($param::Fetch,)*)>;
+        }
+
+        // each member is checked against the access of the system so far, but not against the
+        // other members, the access of every member is only added once all are initialized
+        #[allow(non_snake_case)]
+        unsafe impl<$($param: SystemParamState),*> SystemParamState
+            for ParamSetState<($($param,)*)>
+        {
+            #[inline]
+            fn init(world: &mut World, meta: &mut SystemMeta) -> Self {
+                let len = meta.access.iter_filtered().count();
+                let mut access = Vec::new();
+
+                let state = ($({
+                    let mut member = meta.clone();
+                    let state = $param::init(world, &mut member);
+                    access.extend(member.access.iter_filtered().skip(len).cloned());
+                    state
+                },)*);
+
+                for access in access {
+                    meta.access.add(access);
+                }
+
+                Self {
+                    state,
+                    meta: meta.clone(),
+                }
+            }
+
+            #[inline]
+            fn apply(&mut self, world: &mut World) {
+                let ($($param,)*) = &mut self.state;
+                $($param.apply(world);)*
+            }
+        }
+
+        impl<'w, 's, $($param: SystemParamFetch<'w, 's>),*> SystemParamFetch<'w, 's>
+            for ParamSetState<($($param,)*)>
+        {
+            type Item = ParamSet<'w, 's, ($($param::Item,)*)>;
+
+            #[inline]
+            unsafe fn get_param(
+                &'s mut self,
+                meta: &SystemMeta,
+                world: &'w World,
+                change_tick: u32,
+            ) -> Self::Item {
+                self.meta.last_change_tick = meta.last_change_tick;
+
+                ParamSet {
+                    state: &mut self.state,
+                    meta: &self.meta,
+                    world,
+                    change_tick,
+                }
+            }
+        }
+
+        unsafe impl<$($param: ReadOnlySystemParamFetch),*> ReadOnlySystemParamFetch
+            for ParamSetState<($($param,)*)>
+        {
+        }
+    };
+    ([$($done:tt)*]) => {
+        impl_param_set!(@ $($done)*);
+    };
+    ([$($done:tt)*] $next:tt $($rest:tt)*) => {
+        impl_param_set!(@ $($done)*);
+        impl_param_set!([$($done)* $next] $($rest)*);
+    };
+}
+
+impl_param_set!([(P0, p0, 0)](P1, p1, 1)(P2, p2, 2)(P3, p3, 3)(P4, p4, 4)(
+    P5, p5, 5
+)(P6, p6, 6)(P7, p7, 7));
+
 fn assert_access_compatibility(
     query_type: &'static str,
     filter_type: &'static str,
@@ -183,7 +473,7 @@ fn assert_access_compatibility(
     meta: &SystemMeta,
     world: &World,
 ) {
-    let conflicts = access.get_conflicts(&meta.access);
+    let conflicts = meta.access.get_conflicts(access);
 
     if conflicts.is_empty() {
         return;
@@ -214,6 +504,7 @@ pub struct Res<'w, T> {
     ticks: &'w ChangeTicks,
     last_change_tick: u32,
     change_tick: u32,
+    component_id: ComponentId,
 }
 
 impl<'w, T> Res<'w, T> {
@@ -228,11 +519,28 @@ impl<'w, T> Res<'w, T> {
             .is_changed(self.last_change_tick, self.change_tick)
     }
 
+    /// Returns the [`ComponentId`] of the resource, for use with id based APIs.
+    #[inline]
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
     #[inline]
     pub fn into_inner(self) -> &'w T {
         self.value
     }
 
+    /// Clones the resource.
+    ///
+    /// This only reads the resource, so it doesn't mark it as changed.
+    #[inline]
+    pub fn cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.clone()
+    }
+
     pub fn ticks(&self) -> &ChangeTicks {
         self.ticks
     }
@@ -307,6 +615,7 @@ impl<'w, 's, T: Resource> SystemParamFetch<'w, 's> for ResState<T> {
             ticks: unsafe { &*ticks },
             last_change_tick: meta.last_change_tick,
             change_tick,
+            component_id: self.component_id,
         }
     }
 }
@@ -323,9 +632,40 @@ pub struct ResMut<'w, T> {
     ticks: &'w mut ChangeTicks,
     last_change_tick: u32,
     change_tick: u32,
+    component_id: ComponentId,
 }
 
 impl<'w, T> ResMut<'w, T> {
+    /// Reborrows `self` as a shorter lived [`ResMut`] sharing the same change ticks.
+    #[inline]
+    pub fn reborrow(&mut self) -> ResMut<'_, T> {
+        ResMut {
+            value: self.value,
+            ticks: self.ticks,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+            component_id: self.component_id,
+        }
+    }
+
+    /// Projects `self` onto a part of the resource returned by `f`.
+    ///
+    /// Calling `f` doesn't mark the resource as changed, only mutably dereferencing the
+    /// returned [`ResMut`] does.
+    #[inline]
+    pub fn filter_map_unchanged<U>(
+        self,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<ResMut<'w, U>> {
+        Some(ResMut {
+            value: f(self.value)?,
+            ticks: self.ticks,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+            component_id: self.component_id,
+        })
+    }
+
     #[inline]
     pub fn is_added(&self) -> bool {
         self.ticks.is_added(self.last_change_tick, self.change_tick)
@@ -337,10 +677,27 @@ impl<'w, T> ResMut<'w, T> {
             .is_changed(self.last_change_tick, self.change_tick)
     }
 
+    /// Returns the [`ComponentId`] of the resource, for use with id based APIs.
+    #[inline]
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
     #[inline]
     pub fn into_inner(self) -> &'w mut T {
         self.value
     }
+
+    /// Clones the resource.
+    ///
+    /// This only reads the resource, so it doesn't mark it as changed.
+    #[inline]
+    pub fn cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.clone()
+    }
 }
 
 impl<'w, T> Deref for ResMut<'w, T> {
@@ -413,6 +770,7 @@ impl<'w, 's, T: Resource> SystemParamFetch<'w, 's> for ResMutState<T> {
             ticks: unsafe { &mut *ticks },
             last_change_tick: meta.last_change_tick,
             change_tick,
+            component_id: self.component_id,
         }
     }
 }
@@ -427,6 +785,7 @@ pub struct ResInit<'w, T> {
     ticks: &'w ChangeTicks,
     last_change_tick: u32,
     change_tick: u32,
+    component_id: ComponentId,
 }
 
 impl<'w, T> ResInit<'w, T> {
@@ -441,10 +800,27 @@ impl<'w, T> ResInit<'w, T> {
             .is_changed(self.last_change_tick, self.change_tick)
     }
 
+    /// Returns the [`ComponentId`] of the resource, for use with id based APIs.
+    #[inline]
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
     #[inline]
     pub fn into_inner(self) -> &'w T {
         self.value
     }
+
+    /// Clones the resource.
+    ///
+    /// This only reads the resource, so it doesn't mark it as changed.
+    #[inline]
+    pub fn cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.clone()
+    }
 }
 
 impl<'w, T> Deref for ResInit<'w, T> {
@@ -518,6 +894,7 @@ impl<'w, 's, T: Resource + FromWorld> SystemParamFetch<'w, 's> for ResInitState<
             ticks: unsafe { &*ticks },
             last_change_tick: meta.last_change_tick,
             change_tick,
+            component_id: self.component_id,
         }
     }
 }
@@ -534,9 +911,40 @@ pub struct ResMutInit<'w, T> {
     ticks: &'w mut ChangeTicks,
     last_change_tick: u32,
     change_tick: u32,
+    component_id: ComponentId,
 }
 
 impl<'w, T> ResMutInit<'w, T> {
+    /// Reborrows `self` as a shorter lived [`ResMutInit`] sharing the same change ticks.
+    #[inline]
+    pub fn reborrow(&mut self) -> ResMutInit<'_, T> {
+        ResMutInit {
+            value: self.value,
+            ticks: self.ticks,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+            component_id: self.component_id,
+        }
+    }
+
+    /// Projects `self` onto a part of the resource returned by `f`.
+    ///
+    /// Calling `f` doesn't mark the resource as changed, only mutably dereferencing the
+    /// returned [`ResMutInit`] does.
+    #[inline]
+    pub fn filter_map_unchanged<U>(
+        self,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<ResMutInit<'w, U>> {
+        Some(ResMutInit {
+            value: f(self.value)?,
+            ticks: self.ticks,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+            component_id: self.component_id,
+        })
+    }
+
     #[inline]
     pub fn is_added(&self) -> bool {
         self.ticks.is_added(self.last_change_tick, self.change_tick)
@@ -548,10 +956,27 @@ impl<'w, T> ResMutInit<'w, T> {
             .is_changed(self.last_change_tick, self.change_tick)
     }
 
+    /// Returns the [`ComponentId`] of the resource, for use with id based APIs.
+    #[inline]
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
     #[inline]
     pub fn into_inner(self) -> &'w mut T {
         self.value
     }
+
+    /// Clones the resource.
+    ///
+    /// This only reads the resource, so it doesn't mark it as changed.
+    #[inline]
+    pub fn cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.clone()
+    }
 }
 
 impl<'w, T> Deref for ResMutInit<'w, T> {
@@ -626,6 +1051,7 @@ impl<'w, 's, T: Resource + FromWorld> SystemParamFetch<'w, 's> for ResMutInitSta
             ticks: unsafe { &mut *ticks },
             last_change_tick: meta.last_change_tick,
             change_tick,
+            component_id: self.component_id,
         }
     }
 }
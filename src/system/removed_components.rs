@@ -0,0 +1,105 @@
+use std::marker::PhantomData;
+
+use crate::{
+    event::ManualEventReader,
+    world::{Component, ComponentId, Entity, World},
+};
+
+use super::{
+    ReadOnlySystemParamFetch, SystemMeta, SystemParam, SystemParamFetch, SystemParamState,
+};
+
+/// A [`SystemParam`] yielding the entities that component `T` was removed from since the system
+/// last ran, including despawned entities that had `T`.
+///
+/// Removals are buffered like [`Events`](crate::event::Events) updated by
+/// [`World::clear_trackers`], so a system sees every removal as long as it runs at least once
+/// between two calls.
+///
+/// # Example
+/// ```rust
+/// # use shiv::{prelude::*, system::RemovedComponents};
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// fn on_death(mut removed: RemovedComponents<Health>) {
+///     for entity in removed.iter() {
+///         println!("{} lost its health", entity);
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RemovedComponents<'w, 's, T: Component> {
+    reader: &'s mut ManualEventReader<Entity>,
+    world: &'w World,
+    id: ComponentId,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'w, 's, T: Component> RemovedComponents<'w, 's, T> {
+    /// Iterates the entities `T` was removed from that this system hasn't seen yet, oldest
+    /// first.
+    #[inline]
+    pub fn iter(&mut self) -> impl DoubleEndedIterator<Item = Entity> + '_ {
+        let removed = self.world.removed_components.get(self.id.index());
+        let entities = removed.map(|removed| self.reader.iter(removed).copied());
+        entities.into_iter().flatten()
+    }
+
+    /// Returns `true` if there are no unseen removals.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        let removed = self.world.removed_components.get(self.id.index());
+        removed.map_or(true, |removed| self.reader.is_empty(removed))
+    }
+
+    /// Marks every removal as seen, without iterating them.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.iter().for_each(drop);
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct RemovedComponentsState<T: Component> {
+    reader: ManualEventReader<Entity>,
+    id: ComponentId,
+    marker: PhantomData<fn() -> T>,
+}
+
+// components are only removed with `&mut World`, so reading removals never conflicts
+unsafe impl<T: Component> SystemParamState for RemovedComponentsState<T> {
+    #[inline]
+    fn init(world: &mut World, _meta: &mut SystemMeta) -> Self {
+        Self {
+            reader: ManualEventReader::default(),
+            id: world.init_component::<T>(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'w, 's, T: Component> SystemParamFetch<'w, 's> for RemovedComponentsState<T> {
+    type Item = RemovedComponents<'w, 's, T>;
+
+    #[inline]
+    unsafe fn get_param(
+        &'s mut self,
+        _meta: &SystemMeta,
+        world: &'w World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        RemovedComponents {
+            reader: &mut self.reader,
+            world,
+            id: self.id,
+            marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<T: Component> ReadOnlySystemParamFetch for RemovedComponentsState<T> {}
+
+impl<'w, 's, T: Component> SystemParam for RemovedComponents<'w, 's, T> {
+    type Fetch = RemovedComponentsState<T>;
+}
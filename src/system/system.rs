@@ -7,16 +7,16 @@ use crate::{
 };
 
 use super::{
-    FilteredAccess, ReadOnlySystemParamFetch, SystemParam, SystemParamFetch, SystemParamItem,
+    FilteredAccessSet, ReadOnlySystemParamFetch, SystemParam, SystemParamFetch, SystemParamItem,
     SystemParamState,
 };
 
 pub type BoxedSystem<In, Out> = Box<dyn System<In = In, Out = Out>>;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SystemMeta {
     pub name: Cow<'static, str>,
-    pub access: FilteredAccess<ComponentId>,
+    pub access: FilteredAccessSet<ComponentId>,
     pub last_change_tick: u32,
 }
 
@@ -25,11 +25,18 @@ impl SystemMeta {
     pub fn new<T>() -> Self {
         Self {
             name: std::any::type_name::<T>().into(),
-            access: FilteredAccess::default(),
+            access: FilteredAccessSet::default(),
             last_change_tick: 0,
         }
     }
 
+    /// Grows `access` to fit every component currently in `world`, so it doesn't have to be
+    /// reallocated for each parameter during initialization.
+    #[inline]
+    pub fn grow_access(&mut self, world: &World) {
+        self.access.grow(world.components.len());
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         &self.name
@@ -48,6 +55,7 @@ impl<Param: SystemParam + 'static> SystemState<Param> {
     pub fn new(world: &mut World) -> Self {
         let mut meta = SystemMeta::new::<Param>();
         meta.last_change_tick = world.change_tick().wrapping_sub(MAX_CHANGE_AGE);
+        meta.grow_access(world);
         let param_state = <Param::Fetch as SystemParamState>::init(world, &mut meta);
         let world_id = world.id();
 
@@ -72,6 +80,7 @@ impl<Param: SystemParam + 'static> SystemState<Param> {
         self.meta.last_change_tick = self.get_last_change_tick(world);
 
         self.meta.access.clear();
+        self.meta.grow_access(world);
         <Param::Fetch as SystemParamState>::init(world, &mut self.meta);
 
         self.world_id = world.id();
@@ -143,6 +152,10 @@ impl<Param: SystemParam + 'static> SystemState<Param> {
         }
     }
 
+    /// # Safety
+    /// - `world` must be the world `self` was created with.
+    /// - This function does not check borrow rules, so it's up to the caller to ensure that access
+    ///   is valid.
     #[inline]
     pub unsafe fn get_unchecked_manual<'w, 's>(
         &'s mut self,
@@ -1,6 +1,8 @@
+use std::borrow::Cow;
+
 use crate::world::World;
 
-use super::{IntoSystem, System, SystemMeta};
+use super::{BoxedSystem, IntoSystem, System, SystemMeta};
 
 #[derive(Debug)]
 pub struct PipeSystem<A, B> {
@@ -99,10 +101,105 @@ where
     }
 }
 
+/// A system that runs `S` and discards its output, see [`IntoSystemAdapters::ignore_output`].
+#[derive(Debug)]
+pub struct IgnoreOutputSystem<S> {
+    system: S,
+}
+
+impl<S: System> System for IgnoreOutputSystem<S> {
+    type In = S::In;
+    type Out = ();
+
+    #[inline]
+    fn meta(&self) -> &SystemMeta {
+        self.system.meta()
+    }
+
+    #[inline]
+    unsafe fn meta_mut(&mut self) -> &mut SystemMeta {
+        unsafe { self.system.meta_mut() }
+    }
+
+    #[inline]
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    #[inline]
+    fn init(&mut self, world: &mut World) {
+        self.system.init(world);
+    }
+
+    #[inline]
+    unsafe fn run_unchecked(&mut self, input: Self::In, world: &World) {
+        unsafe { self.system.run_unchecked(input, world) };
+    }
+
+    #[inline]
+    fn run(&mut self, input: Self::In, world: &mut World) {
+        self.system.run(input, world);
+    }
+
+    #[inline]
+    fn apply(&mut self, world: &mut World) {
+        self.system.apply(world);
+    }
+
+    #[inline]
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    #[inline]
+    fn set_last_change_tick(&mut self, last_change_tick: u32) {
+        self.system.set_last_change_tick(last_change_tick);
+    }
+}
+
+/// Adapters for storing and composing systems dynamically.
+pub trait IntoSystemAdapters<In, Out, Params>: IntoSystem<In, Out, Params> {
+    /// Converts `self` into a [`BoxedSystem`].
+    fn system(self) -> BoxedSystem<In, Out>;
+
+    /// Discards the output of `self`, so it fits where a system returning `()` is expected.
+    fn ignore_output(self) -> IgnoreOutputSystem<Self::System>;
+
+    /// Sets the [`SystemMeta::name`] of `self`.
+    fn named(self, name: impl Into<Cow<'static, str>>) -> Self::System;
+}
+
+impl<In, Out, Params, S> IntoSystemAdapters<In, Out, Params> for S
+where
+    S: IntoSystem<In, Out, Params>,
+{
+    #[inline]
+    fn system(self) -> BoxedSystem<In, Out> {
+        Box::new(self.into_system())
+    }
+
+    #[inline]
+    fn ignore_output(self) -> IgnoreOutputSystem<Self::System> {
+        IgnoreOutputSystem {
+            system: self.into_system(),
+        }
+    }
+
+    #[inline]
+    fn named(self, name: impl Into<Cow<'static, str>>) -> Self::System {
+        let mut system = self.into_system();
+
+        // SAFETY: `SystemMeta::access` is not modified
+        unsafe { system.meta_mut() }.name = name.into();
+
+        system
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        system::{In, IntoPipeSystem, System},
+        system::{BoxedSystem, In, IntoPipeSystem, IntoSystemAdapters, ResMut, System},
         world::World,
     };
 
@@ -125,4 +222,29 @@ mod tests {
 
         assert_eq!(out, 9.0);
     }
+
+    #[test]
+    fn adapters() {
+        fn fallible(mut count: ResMut<u32>) -> Result<(), &'static str> {
+            *count += 1;
+            Err("failed")
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0u32);
+
+        let mut systems: Vec<BoxedSystem<(), ()>> = vec![
+            fallible.ignore_output().system(),
+            fallible.named("fallible").ignore_output().system(),
+        ];
+
+        for system in systems.iter_mut() {
+            system.init(&mut world);
+            system.run((), &mut world);
+            system.apply(&mut world);
+        }
+
+        assert_eq!(*world.resource::<u32>(), 2);
+        assert_eq!(systems[1].meta().name(), "fallible");
+    }
 }
@@ -6,13 +6,68 @@ use std::{
 
 use crate::{
     hash_map::HashMap,
-    storage::{ComponentStorage, DenseStorage, Resource, StorageSet, StorageType, Storages},
+    storage::{
+        ComponentStorage, DenseStorage, Resource, SparseStorage, StorageSet, StorageType, Storages,
+    },
+    world::{Entity, World},
 };
 
 pub use shiv_macro::Component;
 
 pub trait Component: Send + Sync + 'static {
+    /// Where components of this type are stored, [`DenseStorage`] unless derived with
+    /// `#[component(storage = "SparseSet")]`, which selects [`SparseStorage`].
     type Storage: Storage;
+
+    /// Registers the [`ComponentHooks`] for this component, called once when the component is
+    /// initialized in a [`World`].
+    #[inline]
+    fn register_hooks(_hooks: &mut ComponentHooks) {}
+}
+
+/// A hook run by the [`World`] for a component on an `entity`.
+pub type ComponentHook = fn(&mut World, Entity);
+
+/// Hooks run by the [`World`] when a component is modified.
+///
+/// These are usually registered with `#[component(on_insert = path::to_fn)]` when deriving
+/// [`Component`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ComponentHooks {
+    on_insert: Option<ComponentHook>,
+    on_replace: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Sets the hook run after the component has been inserted on an entity.
+    #[inline]
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_insert = Some(hook);
+        self
+    }
+
+    /// Sets the hook run before the component is replaced by a new value, while the old value
+    /// is still present on the entity.
+    #[inline]
+    pub fn on_replace(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_replace = Some(hook);
+        self
+    }
+
+    #[inline]
+    pub const fn get_on_insert(&self) -> Option<ComponentHook> {
+        self.on_insert
+    }
+
+    #[inline]
+    pub const fn get_on_replace(&self) -> Option<ComponentHook> {
+        self.on_replace
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.on_insert.is_none() && self.on_replace.is_none()
+    }
 }
 
 pub trait Storage: ComponentStorage + Sized {
@@ -36,6 +91,20 @@ impl Storage for DenseStorage {
     }
 }
 
+impl Storage for SparseStorage {
+    const STORAGE_TYPE: StorageType = StorageType::SparseSet;
+
+    #[inline]
+    fn get(storage: &Storages) -> &StorageSet<Self> {
+        &storage.sparse
+    }
+
+    #[inline]
+    fn get_mut(storage: &mut Storages) -> &mut StorageSet<Self> {
+        &mut storage.sparse
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ComponentDescriptor {
     name: Cow<'static, str>,
@@ -90,6 +159,8 @@ impl ComponentDescriptor {
 pub struct ComponentInfo {
     pub id: ComponentId,
     pub descriptor: ComponentDescriptor,
+    pub hooks: ComponentHooks,
+    pub is_resource: bool,
 }
 
 impl ComponentInfo {
@@ -103,6 +174,17 @@ impl ComponentInfo {
         &self.descriptor
     }
 
+    #[inline]
+    pub const fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    /// Returns `true` if `self` was registered as a resource.
+    #[inline]
+    pub const fn is_resource(&self) -> bool {
+        self.is_resource
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         self.descriptor.name()
@@ -125,11 +207,19 @@ impl ComponentInfo {
 
     #[inline]
     fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
-        Self { id, descriptor }
+        Self {
+            id,
+            descriptor,
+            hooks: ComponentHooks::default(),
+            is_resource: false,
+        }
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// A unique identifier for a component or resource in a [`World`].
+///
+/// Ids are ordered by registration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ComponentId(usize);
 
 impl ComponentId {
@@ -180,10 +270,9 @@ impl Components {
         }
 
         let index = self.components.len();
-        self.components.push(ComponentInfo::new(
-            ComponentId::new(index),
-            ComponentDescriptor::new::<T>(),
-        ));
+        let mut info = ComponentInfo::new(ComponentId::new(index), ComponentDescriptor::new::<T>());
+        T::register_hooks(&mut info.hooks);
+        self.components.push(info);
         self.indices.insert(type_id, index);
 
         ComponentId::new(index)
@@ -198,10 +287,12 @@ impl Components {
         }
 
         let index = self.components.len();
-        self.components.push(ComponentInfo::new(
+        let mut info = ComponentInfo::new(
             ComponentId::new(index),
             ComponentDescriptor::new_resource::<T>(),
-        ));
+        );
+        info.is_resource = true;
+        self.components.push(info);
         self.resource_indices.insert(type_id, index);
 
         ComponentId::new(index)
@@ -244,11 +335,31 @@ impl Components {
         self.components.get(id.index())
     }
 
+    /// # Safety
+    /// - `id` must be a component or resource registered in `self`.
     #[inline]
     pub unsafe fn get_unchecked(&self, id: ComponentId) -> &ComponentInfo {
         unsafe { self.components.get_unchecked(id.index()) }
     }
 
+    /// Iterates over every registered component and resource, ordered by [`ComponentId`].
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, ComponentInfo> {
+        self.components.iter()
+    }
+
+    /// Iterates over every registered component, ordered by [`ComponentId`].
+    #[inline]
+    pub fn iter_components(&self) -> impl Iterator<Item = &ComponentInfo> {
+        self.iter().filter(|info| !info.is_resource())
+    }
+
+    /// Iterates over every registered resource, ordered by [`ComponentId`].
+    #[inline]
+    pub fn iter_resources(&self) -> impl Iterator<Item = &ComponentInfo> {
+        self.iter().filter(|info| info.is_resource())
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.components.len()
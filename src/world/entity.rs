@@ -7,6 +7,9 @@ use fixedbitset::FixedBitSet;
 /// Contains an `index` and a `generation`.
 /// When an [`Entity`] is freed, the index is reused,
 /// but the generation is incremented.
+///
+/// Once the generation of an index reaches `u32::MAX`, freeing it retires the index instead,
+/// so a stale [`Entity`] can never alias a new one.
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Entity {
@@ -66,6 +69,13 @@ impl EntityIdSet {
         self.entities.is_empty()
     }
 
+    /// Returns the number of entities in the set, unlike [`EntityIdSet::len`] which is the
+    /// capacity.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.entities.count_ones(..)
+    }
+
     #[inline]
     pub fn resize(&mut self, len: usize, value: bool) {
         let old_len = self.entities.len();
@@ -154,6 +164,19 @@ pub struct Entities {
     len: u32,
 }
 
+impl Clone for Entities {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            meta: self.meta.clone(),
+            entity_id_set: self.entity_id_set.clone(),
+            pending: self.pending.clone(),
+            free_cursor: AtomicIsize::new(self.free_cursor.load(Ordering::Relaxed)),
+            len: self.len,
+        }
+    }
+}
+
 impl Entities {
     #[inline]
     pub fn contains(&self, entity: Entity) -> bool {
@@ -227,6 +250,50 @@ impl Entities {
         }
     }
 
+    /// Allocates `count` entities at once, appending them to `entities`.
+    ///
+    /// Free indices are reused first, like [`Entities::alloc`], the metadata of the remaining
+    /// new indices is allocated in one go.
+    #[inline]
+    pub fn alloc_batch(&mut self, count: usize, entities: &mut Vec<Entity>) {
+        self.flush();
+        entities.reserve(count);
+
+        let reused = usize::min(count, self.pending.len());
+        let start = self.pending.len() - reused;
+        for index in self.pending.drain(start..).rev() {
+            self.entity_id_set.insert(index as usize);
+            let meta = &mut self.meta[index as usize];
+            meta.is_empty = false;
+
+            entities.push(Entity {
+                index,
+                generation: meta.generation,
+            });
+        }
+
+        let new_free_cursor = self.pending.len() as isize;
+        *self.free_cursor.get_mut() = new_free_cursor;
+
+        let old_len = self.meta.len();
+        let new_len = old_len + (count - reused);
+        if new_len > old_len {
+            u32::try_from(new_len - 1).expect("too many entities");
+        }
+
+        self.meta.resize(new_len, EntityMeta::default());
+        for index in old_len..new_len {
+            self.entity_id_set.insert(index);
+
+            entities.push(Entity {
+                index: index as u32,
+                generation: 0,
+            });
+        }
+
+        self.len += count as u32;
+    }
+
     #[inline]
     pub fn alloc_at(&mut self, entity: Entity) -> bool {
         self.flush();
@@ -254,6 +321,12 @@ impl Entities {
             self.entity_id_set.insert(entity.index() as usize);
             self.len += 1;
 
+            contains = false;
+        } else if self.meta[entity.index() as usize].is_empty {
+            // the index was retired, see `Entities::free`
+            self.entity_id_set.insert(entity.index() as usize);
+            self.len += 1;
+
             contains = false;
         } else {
             contains = true;
@@ -274,15 +347,18 @@ impl Entities {
             return false;
         };
 
-        if meta.generation != entity.generation() {
+        if meta.generation != entity.generation() || meta.is_empty {
             return false;
         }
-        meta.generation += 1;
         meta.is_empty = true;
 
         self.entity_id_set.remove(entity.index() as usize);
 
-        self.pending.push(entity.index());
+        // an exhausted index is retired rather than wrapping its generation around
+        if let Some(generation) = meta.generation.checked_add(1) {
+            meta.generation = generation;
+            self.pending.push(entity.index());
+        }
 
         let new_free_cursor = self.pending.len() as isize;
         *self.free_cursor.get_mut() = new_free_cursor;
@@ -1,3 +1,5 @@
+use std::mem;
+
 use crate::{bundle::Bundle, change_detection::Mut};
 
 use super::{Component, Entity, World};
@@ -79,6 +81,20 @@ impl<'w> EntityMut<'w> {
 
     #[inline]
     pub fn insert<T: Bundle>(&mut self, bundle: T) -> &mut Self {
+        let bundle_info = self
+            .world
+            .bundles
+            .init_bundle::<T>(&mut self.world.components);
+
+        // hooks may modify the world, so the component ids need to be copied out
+        let component_ids = if bundle_info.has_hooks() {
+            let component_ids = bundle_info.component_ids().to_vec();
+            self.world.run_replace_hooks(self.entity, &component_ids);
+            Some(component_ids)
+        } else {
+            None
+        };
+
         let change_tick = self.world.change_tick();
         let bundle_info = self
             .world
@@ -94,14 +110,48 @@ impl<'w> EntityMut<'w> {
                 change_tick,
             )
         };
+
+        if let Some(component_ids) = component_ids {
+            self.world.run_insert_hooks(self.entity, &component_ids);
+        }
+
         self
     }
 
+    /// Inserts `component`, returning the component it replaced, if any.
+    ///
+    /// Unlike [`EntityMut::insert`], the replaced component is returned instead of dropped.
+    #[inline]
+    pub fn insert_and_replace<T: Component>(&mut self, component: T) -> Option<T> {
+        let id = self.world.init_component::<T>();
+        self.world.run_replace_hooks(self.entity, &[id]);
+
+        // an `on_replace` hook may have removed the component
+        match self.world.get_mut::<T>(self.entity) {
+            Some(mut current) => {
+                let replaced = mem::replace(&mut *current, component);
+                self.world.run_insert_hooks(self.entity, &[id]);
+                Some(replaced)
+            }
+            None => {
+                self.insert(component);
+                None
+            }
+        }
+    }
+
     #[inline]
     pub fn remove<T: Bundle>(&mut self) -> Option<T> {
         self.world.remove(self.entity)
     }
 
+    /// Removes every component that isn't in the bundle `T`.
+    #[inline]
+    pub fn retain<T: Bundle>(&mut self) -> &mut Self {
+        self.world.retain::<T>(self.entity);
+        self
+    }
+
     #[inline]
     pub fn despawn(self) {
         self.world.despawn(self.entity);
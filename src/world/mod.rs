@@ -3,9 +3,11 @@
 mod component;
 mod entity;
 mod entity_ref;
+mod snapshot;
 mod world;
 
 pub use component::*;
 pub use entity::*;
 pub use entity_ref::*;
+pub use snapshot::*;
 pub use world::*;
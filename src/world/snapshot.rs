@@ -0,0 +1,295 @@
+use std::{any::TypeId, mem};
+
+use crate::storage::{ComponentStorage, Resource};
+
+use super::{Component, Entities, Entity, Storage, World};
+
+/// The captured state of a single registered component or resource type.
+trait SnapshotData: Send + Sync {
+    fn restore(&self, world: &mut World);
+}
+
+struct ComponentSnapshot<T> {
+    components: Vec<(Entity, T)>,
+}
+
+impl<T: Component + Clone> ComponentSnapshot<T> {
+    #[inline]
+    fn capture(world: &World) -> Box<dyn SnapshotData> {
+        let storage = world.components.get_component::<T>().and_then(|id| {
+            let storage_sets = <T::Storage as Storage>::get(&world.storage);
+            storage_sets.get(id)
+        });
+
+        let components = storage.into_iter().flat_map(|storage| {
+            let entities = world.entities.entity_ids().iter();
+
+            entities.filter_map(move |index| {
+                let entity = world.entities.get(index)?;
+                let component = storage.get(entity)?;
+
+                // SAFETY: `storage` stores components of type `T`.
+                Some((entity, unsafe { (*(component as *const T)).clone() }))
+            })
+        });
+
+        Box::new(Self {
+            components: components.collect(),
+        })
+    }
+}
+
+impl<T: Component + Clone> SnapshotData for ComponentSnapshot<T> {
+    #[inline]
+    fn restore(&self, world: &mut World) {
+        let id = world.init_component::<T>();
+        let change_tick = world.change_tick();
+
+        let storage_sets = <T::Storage as Storage>::get_mut(&mut world.storage);
+        // SAFETY: `init_component` initialized the storage of `id`.
+        let storage = unsafe { storage_sets.get_unchecked_mut(id) };
+
+        for (entity, component) in &self.components {
+            let mut component = component.clone();
+
+            // SAFETY: `storage` stores components of type `T`.
+            unsafe { storage.insert(*entity, &mut component as *mut T as *mut u8, change_tick) };
+            mem::forget(component);
+        }
+    }
+}
+
+struct ResourceSnapshot<T> {
+    resource: Option<T>,
+}
+
+impl<T: Resource + Clone> ResourceSnapshot<T> {
+    #[inline]
+    fn capture(world: &World) -> Box<dyn SnapshotData> {
+        Box::new(Self {
+            resource: world.get_resource::<T>().cloned(),
+        })
+    }
+}
+
+impl<T: Resource + Clone> SnapshotData for ResourceSnapshot<T> {
+    #[inline]
+    fn restore(&self, world: &mut World) {
+        match self.resource {
+            Some(ref resource) => world.insert_resource(resource.clone()),
+            None => drop(world.remove_resource::<T>()),
+        }
+    }
+}
+
+type CaptureFn = fn(&World) -> Box<dyn SnapshotData>;
+
+/// A resource storing the component and resource types captured by [`World::snapshot`].
+///
+/// Types are added with [`World::register_snapshot_component`] and
+/// [`World::register_snapshot_resource`].
+#[derive(Default)]
+pub struct SnapshotRegistry {
+    components: Vec<(TypeId, CaptureFn)>,
+    resources: Vec<(TypeId, CaptureFn)>,
+}
+
+impl SnapshotRegistry {
+    #[inline]
+    pub fn contains_component<T: Component>(&self) -> bool {
+        let type_id = TypeId::of::<T>();
+        self.components.iter().any(|&(id, _)| id == type_id)
+    }
+
+    #[inline]
+    pub fn contains_resource<T: Resource>(&self) -> bool {
+        let type_id = TypeId::of::<T>();
+        self.resources.iter().any(|&(id, _)| id == type_id)
+    }
+}
+
+impl std::fmt::Debug for SnapshotRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotRegistry")
+            .field("components", &self.components.len())
+            .field("resources", &self.resources.len())
+            .finish()
+    }
+}
+
+/// The state of a [`World`] captured by [`World::snapshot`], restored by [`World::restore`].
+///
+/// Contains every entity, the components of every type registered with
+/// [`World::register_snapshot_component`] and the resources registered with
+/// [`World::register_snapshot_resource`].
+pub struct WorldSnapshot {
+    entities: Entities,
+    data: Vec<Box<dyn SnapshotData>>,
+}
+
+impl WorldSnapshot {
+    #[inline]
+    pub fn entities(&self) -> &Entities {
+        &self.entities
+    }
+}
+
+impl std::fmt::Debug for WorldSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorldSnapshot")
+            .field("entities", &self.entities.len())
+            .field("types", &self.data.len())
+            .finish()
+    }
+}
+
+impl World {
+    /// Registers the component `T` to be captured by [`World::snapshot`].
+    #[inline]
+    pub fn register_snapshot_component<T: Component + Clone>(&mut self) {
+        let mut registry = self.resource_or_default::<SnapshotRegistry>();
+
+        if !registry.contains_component::<T>() {
+            let capture: CaptureFn = ComponentSnapshot::<T>::capture;
+            registry.components.push((TypeId::of::<T>(), capture));
+        }
+    }
+
+    /// Registers the resource `T` to be captured by [`World::snapshot`].
+    ///
+    /// Resources are only captured when registered, since most aren't part of the simulated
+    /// state, e.g. the [`SnapshotRegistry`] itself.
+    #[inline]
+    pub fn register_snapshot_resource<T: Resource + Clone>(&mut self) {
+        let mut registry = self.resource_or_default::<SnapshotRegistry>();
+
+        if !registry.contains_resource::<T>() {
+            let capture: CaptureFn = ResourceSnapshot::<T>::capture;
+            registry.resources.push((TypeId::of::<T>(), capture));
+        }
+    }
+
+    /// Captures every entity along with its registered components, and every registered
+    /// resource, see [`World::register_snapshot_component`].
+    ///
+    /// Components and resources are cloned, so this is `O(n)` in the number of captured
+    /// components.
+    #[inline]
+    pub fn snapshot(&self) -> WorldSnapshot {
+        let mut data = Vec::new();
+
+        if let Some(registry) = self.get_resource::<SnapshotRegistry>() {
+            let captures = registry.components.iter().chain(&registry.resources);
+            data.extend(captures.map(|(_, capture)| capture(self)));
+        }
+
+        WorldSnapshot {
+            entities: self.entities.clone(),
+            data,
+        }
+    }
+
+    /// Restores the world to the state captured in `snapshot`.
+    ///
+    /// Every entity is despawned, and the entities of `snapshot` are restored with the same
+    /// indices and generations, so [`Entity`]s held in components stay valid. Components not
+    /// registered when `snapshot` was taken are lost, and restored components are marked as
+    /// added.
+    ///
+    /// Neither hooks nor observers are run and no removals are recorded for
+    /// [`RemovedComponents`](crate::system::RemovedComponents), since the entities aren't
+    /// semantically despawned.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.flush();
+
+        for index in self.entities.entity_ids().iter() {
+            // SAFETY: every index in `entity_ids` is contained in `self.entities`.
+            let entity = unsafe { self.entities.get_unchecked(index) };
+            self.storage.remove(entity, |_| {});
+        }
+
+        self.entities = snapshot.entities.clone();
+
+        for data in &snapshot.data {
+            data.restore(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as shiv;
+    use crate::world::{Component, Entity, World};
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Position(f32, f32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Target(Entity);
+
+    #[derive(Component, Debug)]
+    struct Unregistered;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Frame(u32);
+
+    #[test]
+    fn snapshot_restore() {
+        let mut world = World::new();
+        world.register_snapshot_component::<Position>();
+        world.register_snapshot_component::<Target>();
+        world.register_snapshot_resource::<Frame>();
+        world.insert_resource(Frame(1));
+
+        let a = world.spawn().insert(Position(0.0, 1.0)).entity();
+        let b = world
+            .spawn()
+            .insert(Position(2.0, 3.0))
+            .insert(Target(a))
+            .entity();
+        let despawned = world.spawn().entity();
+        world.despawn(despawned);
+
+        let snapshot = world.snapshot();
+        assert_eq!(snapshot.entities().len(), 2);
+
+        world.get_mut::<Position>(a).unwrap().0 = 10.0;
+        world.entity_mut(b).remove::<Target>();
+        world.despawn(a);
+        let c = world.spawn().insert(Position(5.0, 5.0)).entity();
+        world.entity_mut(b).insert(Unregistered);
+        world.resource_mut::<Frame>().0 = 2;
+
+        world.restore(&snapshot);
+
+        assert!(world.contains_entity(a));
+        assert!(world.contains_entity(b));
+        assert!(!world.contains_entity(c));
+        assert!(!world.contains_entity(despawned));
+        assert_eq!(world.entities().len(), 2);
+
+        assert_eq!(world.get::<Position>(a), Some(&Position(0.0, 1.0)));
+        assert_eq!(world.get::<Position>(b), Some(&Position(2.0, 3.0)));
+        assert_eq!(world.get::<Target>(a), None);
+        assert_eq!(world.get::<Target>(b), Some(&Target(a)));
+        assert!(!world.contains::<Unregistered>(b));
+        assert_eq!(*world.resource::<Frame>(), Frame(1));
+
+        // the allocator is restored too, so spawning is deterministic
+        let spawned = world.spawn().entity();
+        world.restore(&snapshot);
+        assert_eq!(world.spawn().entity(), spawned);
+    }
+
+    #[test]
+    fn restore_removes_resource() {
+        let mut world = World::new();
+        world.register_snapshot_resource::<Frame>();
+
+        let snapshot = world.snapshot();
+        world.insert_resource(Frame(1));
+        world.restore(&snapshot);
+
+        assert!(!world.contains_resource::<Frame>());
+    }
+}
@@ -1,14 +1,41 @@
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use hyena::TaskPool;
 
 use crate::{
     bundle::{Bundle, Bundles},
-    change_detection::{Mut, Ticks},
-    query::{QueryState, ReadOnlyWorldQuery, WorldQuery},
-    storage::{ComponentStorage, Resource, Storages},
+    change_detection::{ChangeTicks, Mut, Ticks, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
+    event::{Events, Observers, OnDespawn},
+    query::{QueryItem, QueryState, ReadOnlyWorldQuery, WorldQuery},
+    storage::{ComponentStorage, Resource, SparseArray, Storages},
+    system::CommandErrors,
     world::Entities,
 };
 
-use super::{Component, ComponentId, Components, Entity, EntityMut, EntityRef, Storage};
+use super::{
+    Component, ComponentId, ComponentInfo, Components, Entity, EntityMut, EntityRef, Storage,
+};
+
+/// An error returned by [`World::get_or_spawn_with_generation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpawnError {
+    /// The index is used by this live entity, which has a different generation.
+    IndexInUse(Entity),
+}
+
+impl Display for SpawnError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IndexInUse(entity) => write!(f, "entity index is in use by {}", entity),
+        }
+    }
+}
+
+impl std::error::Error for SpawnError {}
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WorldId(usize);
@@ -45,6 +72,10 @@ pub struct World {
     pub(crate) components: Components,
     pub(crate) change_tick: AtomicU32,
     pub(crate) last_change_tick: u32,
+    last_check_tick: u32,
+    /// The entities each component was removed from, indexed by [`ComponentId`], see
+    /// [`RemovedComponents`](crate::system::RemovedComponents).
+    pub(crate) removed_components: SparseArray<Events<Entity>>,
 }
 
 unsafe impl Send for World {}
@@ -61,6 +92,8 @@ impl Default for World {
             components: Components::default(),
             change_tick: AtomicU32::new(1),
             last_change_tick: 0,
+            last_check_tick: 0,
+            removed_components: SparseArray::new(),
         }
     }
 }
@@ -76,6 +109,11 @@ impl World {
         self.id
     }
 
+    #[inline]
+    pub fn components(&self) -> &Components {
+        &self.components
+    }
+
     #[inline]
     pub fn entities(&self) -> &Entities {
         &self.entities
@@ -106,6 +144,33 @@ impl World {
 
         id
     }
+
+    /// Runs the [`ComponentHooks::on_replace`] hooks for every component in `component_ids`
+    /// that `entity` already has.
+    #[inline]
+    pub(crate) fn run_replace_hooks(&mut self, entity: Entity, component_ids: &[ComponentId]) {
+        for &id in component_ids {
+            let info = unsafe { self.components.get_unchecked(id) };
+
+            if let Some(hook) = info.hooks().get_on_replace() {
+                if self.storage.contains(id, entity) {
+                    hook(self, entity);
+                }
+            }
+        }
+    }
+
+    /// Runs the [`ComponentHooks::on_insert`] hooks for every component in `component_ids`.
+    #[inline]
+    pub(crate) fn run_insert_hooks(&mut self, entity: Entity, component_ids: &[ComponentId]) {
+        for &id in component_ids {
+            let info = unsafe { self.components.get_unchecked(id) };
+
+            if let Some(hook) = info.hooks().get_on_insert() {
+                hook(self, entity);
+            }
+        }
+    }
 }
 
 impl World {
@@ -118,6 +183,19 @@ impl World {
     pub fn query_filtered<Q: WorldQuery, F: ReadOnlyWorldQuery>(&mut self) -> QueryState<Q, F> {
         QueryState::new(self)
     }
+
+    /// Runs `func` on every item of `Q` in parallel on `task_pool`, in batches of `batch_size`
+    /// entities, see [`QueryState::par_for_each_mut`].
+    #[inline]
+    pub fn par_iter_mut<Q: WorldQuery>(
+        &mut self,
+        task_pool: &TaskPool,
+        batch_size: usize,
+        func: impl Fn(QueryItem<'_, Q>) + Send + Sync,
+    ) {
+        let mut state = QueryState::<Q, ()>::new(self);
+        state.par_for_each_mut(self, task_pool, batch_size, func);
+    }
 }
 
 impl World {
@@ -208,14 +286,61 @@ impl World {
 }
 
 impl World {
+    /// Spawns an empty entity.
+    ///
+    /// The entity is allocated immediately, unlike
+    /// [`Commands::spawn`](crate::system::Commands::spawn) and [`World::reserve_entity`], which
+    /// only reserve an entity that becomes live on the next [`World::flush`].
     #[inline]
     pub fn spawn(&mut self) -> EntityMut<'_> {
         let entity = self.entities.alloc();
         EntityMut::new(self, entity)
     }
 
+    /// Spawns an empty entity, an alias of [`World::spawn`].
+    #[inline]
+    pub fn spawn_empty(&mut self) -> EntityMut<'_> {
+        self.spawn()
+    }
+
+    /// Makes `entity`, previously returned by [`World::reserve_entity`], live by flushing
+    /// reserved entities.
+    ///
+    /// # Panics
+    /// - `entity` was never reserved, or has already been despawned.
+    #[inline]
+    #[track_caller]
+    pub fn spawn_reserved(&mut self, entity: Entity) -> EntityMut<'_> {
+        self.flush();
+
+        if !self.contains_entity(entity) {
+            panic!(
+                "Attempting to spawn reserved entity {}, which was not reserved.",
+                entity,
+            );
+        }
+
+        EntityMut::new(self, entity)
+    }
+
+    /// Spawns an entity with `bundle` already inserted.
+    #[inline]
+    pub fn spawn_with<T: Bundle>(&mut self, bundle: T) -> EntityMut<'_> {
+        let mut entity = self.spawn();
+        entity.insert(bundle);
+        entity
+    }
+
+    /// Gets `entity`, spawning it if it doesn't exist.
+    ///
+    /// If the index of `entity` is used by a live entity with a different generation, that
+    /// entity is despawned first.
     #[inline]
     pub fn get_or_spawn(&mut self, entity: Entity) -> EntityMut<'_> {
+        // reserved entities must be live before checking, otherwise `alloc_at` would reuse
+        // the index of a reserved entity without despawning it
+        self.flush();
+
         if self.contains_entity(entity) {
             EntityMut::new(self, entity)
         } else {
@@ -228,10 +353,159 @@ impl World {
         }
     }
 
+    /// Gets `entity`, spawning it with exactly the requested index and generation if it
+    /// doesn't exist.
+    ///
+    /// Unlike [`World::get_or_spawn`], a live entity using the same index is never despawned,
+    /// instead [`SpawnError::IndexInUse`] is returned.
+    #[inline]
+    pub fn get_or_spawn_with_generation(
+        &mut self,
+        entity: Entity,
+    ) -> Result<EntityMut<'_>, SpawnError> {
+        self.flush();
+
+        if self.contains_entity(entity) {
+            return Ok(EntityMut::new(self, entity));
+        }
+
+        if let Some(live) = self.entities.get(entity.index() as usize) {
+            return Err(SpawnError::IndexInUse(live));
+        }
+
+        self.entities.alloc_at(entity);
+        Ok(EntityMut::new(self, entity))
+    }
+
+    /// Spawns an entity for every bundle in `iter`, returning the spawned entities in order.
+    ///
+    /// This is faster than spawning entities one by one, since the [`BundleInfo`] of `B` is
+    /// only looked up once, and the entities `iter` is known to yield by its size hint are
+    /// allocated up front.
+    ///
+    /// [`BundleInfo`]: crate::bundle::BundleInfo
+    pub fn spawn_batch<I>(&mut self, iter: I) -> Vec<Entity>
+    where
+        I: IntoIterator,
+        I::Item: Bundle,
+    {
+        let iter = iter.into_iter();
+        let mut entities = Vec::with_capacity(iter.size_hint().0);
+
+        let change_tick = self.change_tick();
+        let bundle_info = self.bundles.init_bundle::<I::Item>(&mut self.components);
+        if bundle_info.has_hooks() {
+            entities.extend(iter.map(|bundle| self.spawn_with(bundle).entity()));
+            return entities;
+        }
+
+        self.entities.alloc_batch(iter.size_hint().0, &mut entities);
+
+        let mut len = 0;
+        for bundle in iter {
+            if len == entities.len() {
+                entities.push(self.entities.alloc());
+            }
+
+            let entity = entities[len];
+            len += 1;
+
+            // SAFETY: `bundle_info` was created for `I::Item` with `self.components`.
+            unsafe {
+                bundle_info.insert(
+                    entity,
+                    bundle,
+                    &mut self.components,
+                    &mut self.storage,
+                    change_tick,
+                )
+            };
+        }
+
+        // `iter` yielded fewer bundles than its size hint promised
+        for entity in entities.drain(len..) {
+            self.entities.free(entity);
+        }
+
+        entities
+    }
+
+    /// Inserts every bundle in `iter` into its entity, spawning entities that don't exist with
+    /// [`World::get_or_spawn`] semantics, so ids can be assigned deterministically.
+    pub fn insert_or_spawn_batch<I, B>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (Entity, B)>,
+        B: Bundle,
+    {
+        // reserved entities must be live before checking, see `World::get_or_spawn`
+        self.flush();
+
+        let bundle_info = self.bundles.init_bundle::<B>(&mut self.components);
+        if bundle_info.has_hooks() {
+            for (entity, bundle) in iter {
+                self.get_or_spawn(entity).insert(bundle);
+            }
+
+            return;
+        }
+
+        // cloned so `self` can be borrowed mutably to despawn entities in the way
+        let bundle_info = bundle_info.clone();
+        let change_tick = self.change_tick();
+
+        for (entity, bundle) in iter {
+            if !self.contains_entity(entity) {
+                if let Some(live) = self.entities.get(entity.index() as usize) {
+                    self.despawn(live);
+                }
+
+                self.entities.alloc_at(entity);
+            }
+
+            // SAFETY: `bundle_info` was created for `B` with `self.components`.
+            unsafe {
+                bundle_info.insert(
+                    entity,
+                    bundle,
+                    &mut self.components,
+                    &mut self.storage,
+                    change_tick,
+                )
+            };
+        }
+    }
+
     #[inline]
     pub fn remove<T: Bundle>(&mut self, entity: Entity) -> Option<T> {
         let bundle_info = self.bundles.init_bundle::<T>(&mut self.components);
-        unsafe { bundle_info.remove::<T>(entity, &mut self.components, &mut self.storage) }
+        let bundle =
+            unsafe { bundle_info.remove::<T>(entity, &mut self.components, &mut self.storage) }?;
+
+        for &id in bundle_info.component_ids() {
+            let removed = self.removed_components.get_or_default(id.index());
+            removed.send(entity);
+        }
+
+        Some(bundle)
+    }
+
+    /// Removes and drops every component of `entity` that isn't in the bundle `T`.
+    #[inline]
+    pub fn retain<T: Bundle>(&mut self, entity: Entity) {
+        let bundle_info = self.bundles.init_bundle::<T>(&mut self.components);
+        let keep = bundle_info.component_ids();
+
+        let components = self.components.iter_components().map(ComponentInfo::id);
+        let remove = components
+            .filter(|id| !keep.contains(id) && self.storage.contains(*id, entity))
+            .collect::<Vec<_>>();
+
+        for id in remove {
+            self.storage.remove_and_drop(entity, id);
+            self.removed_components
+                .get_or_default(id.index())
+                .send(entity);
+        }
     }
 
     #[inline]
@@ -292,6 +566,9 @@ impl World {
         storage.contains(entity)
     }
 
+    /// Gets a reference to the component `T` of `entity`.
+    ///
+    /// Returns `None` if `entity` doesn't exist or doesn't have `T`.
     #[inline]
     pub fn get<T: Component>(&self, entity: Entity) -> Option<&T> {
         let id = self.components.get_component::<T>()?;
@@ -303,6 +580,25 @@ impl World {
         Some(unsafe { &*(ptr as *const T) })
     }
 
+    /// Gets the [`ChangeTicks`] of component `T` on `entity`.
+    #[inline]
+    pub fn get_change_ticks<T: Component>(&self, entity: Entity) -> Option<ChangeTicks> {
+        let id = self.components.get_component::<T>()?;
+
+        let storage_sets = <T::Storage as Storage>::get(&self.storage);
+        let storage = unsafe { storage_sets.get_unchecked(id) };
+
+        if !storage.contains(entity) {
+            return None;
+        }
+
+        let ticks = unsafe { storage.get_ticks_unchecked(entity) };
+        Some(unsafe { *ticks.get() })
+    }
+
+    /// Gets a change-tracked mutable reference to the component `T` of `entity`.
+    ///
+    /// Returns `None` if `entity` doesn't exist or doesn't have `T`.
     #[inline]
     pub fn get_mut<T: Component>(&mut self, entity: Entity) -> Option<Mut<'_, T>> {
         let id = self.components.get_component::<T>()?;
@@ -323,23 +619,120 @@ impl World {
         })
     }
 
+    /// Gets a pointer to the component `id` of `entity`, for when the component type isn't
+    /// known statically.
+    ///
+    /// The pointer is valid for as long as `self` isn't mutated, and points to a value of the
+    /// type described by the [`ComponentInfo`] of `id`.
+    #[inline]
+    pub fn get_by_id(&self, entity: Entity, id: ComponentId) -> Option<*const u8> {
+        let storage = self.storage.get(id)?;
+        storage.get(entity).map(|ptr| ptr as *const u8)
+    }
+
+    /// Despawns `entity`, returning `true` if it existed.
+    ///
+    /// [`OnDespawn`] is triggered first, so observers can still read the components of `entity`.
+    ///
+    /// With the `hierarchy` feature, `entity` is removed from its parent's
+    /// [`Children`](crate::hierarchy::Children) and its children are orphaned, use
+    /// [`World::despawn_recursive`] to despawn the children as well.
     #[inline]
     pub fn despawn(&mut self, entity: Entity) -> bool {
-        self.storage.remove(entity);
+        // storages are indexed by entity index only, so a stale `entity` must not reach them
+        if !self.contains_entity(entity) {
+            return false;
+        }
+
+        let observed = self.get_resource::<Observers>();
+        if observed.map_or(false, Observers::contains::<OnDespawn>) {
+            self.trigger(OnDespawn { entity });
+
+            // observers may have despawned `entity`, and spawned another entity reusing its index
+            if !self.contains_entity(entity) {
+                return true;
+            }
+        }
+
+        #[cfg(feature = "hierarchy")]
+        self.detach_hierarchy(entity);
+
+        let removed_components = &mut self.removed_components;
+        self.storage.remove(entity, |id| {
+            removed_components.get_or_default(id.index()).send(entity);
+        });
+
         self.entities.free(entity)
     }
 }
 
 impl World {
+    /// Clamps all change ticks in the world to at most [`MAX_CHANGE_AGE`] ticks old.
+    ///
+    /// This must be called at least once every
+    /// [`CHECK_TICK_THRESHOLD`](crate::change_detection::CHECK_TICK_THRESHOLD) ticks, for
+    /// change detection to remain correct.
     #[inline]
     pub fn check_change_ticks(&mut self) {
         let change_tick = self.change_tick();
 
         self.storage.check_change_ticks(change_tick);
+        self.last_check_tick = change_tick;
+
+        if change_tick.wrapping_sub(self.last_change_tick) > MAX_CHANGE_AGE {
+            self.last_change_tick = change_tick.wrapping_sub(MAX_CHANGE_AGE);
+        }
     }
 
+    /// Calls [`World::check_change_ticks`] if at least
+    /// [`CHECK_TICK_THRESHOLD`](crate::change_detection::CHECK_TICK_THRESHOLD) ticks have passed
+    /// since the last check, returning `true` if it did.
+    ///
+    /// This is cheap to call often, e.g. once per update of an app that doesn't use a
+    /// [`Schedule`](crate::schedule::Schedule).
+    #[inline]
+    pub fn maybe_check_change_ticks(&mut self) -> bool {
+        let ticks_since_check = self.change_tick().wrapping_sub(self.last_check_tick);
+
+        if ticks_since_check >= CHECK_TICK_THRESHOLD {
+            self.check_change_ticks();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reorders component storage by entity index, improving cache locality of iteration.
+    ///
+    /// Despawning entities and removing components scrambles the order of stored components,
+    /// this is an opt-in way to undo that, e.g. during a loading screen.
+    #[inline]
+    pub fn optimize_storage(&mut self) {
+        self.storage.sort_by_entity();
+    }
+
+    /// Clears change trackers, so changes made after this aren't confused with earlier ones.
+    ///
+    /// This increments the change tick, since systems mark changes with the tick current when
+    /// they started running, and checks change ticks when needed, see
+    /// [`World::maybe_check_change_ticks`].
+    ///
+    /// Removals recorded for [`RemovedComponents`](crate::system::RemovedComponents) and errors
+    /// recorded in [`CommandErrors`] are kept until the second call after they happened, like
+    /// [`Events`] updated once per call.
     pub fn clear_trackers(&mut self) {
-        self.last_change_tick = self.change_tick();
+        self.last_change_tick = self.increment_change_tick();
+        self.maybe_check_change_ticks();
+
+        for (_, removed) in self.removed_components.iter_mut() {
+            removed.update();
+        }
+
+        if let Some(mut errors) = self.get_resource_mut::<CommandErrors>() {
+            if !errors.is_empty() {
+                errors.update();
+            }
+        }
     }
 }
 
@@ -379,12 +772,15 @@ impl std::fmt::Debug for World {
 mod tests {
     use shiv_macro::Bundle;
 
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use crate as shiv;
     use crate::query::Or;
     use crate::{
-        query::{With, Without},
+        change_detection::{ChangeTicks, CHECK_TICK_THRESHOLD},
+        query::{Changed, DynamicQuery, QueryEntityError, QuerySingleError, With, Without},
         storage::DenseStorage,
-        world::{Component, Entity, World},
+        world::{Component, Entity, SpawnError, World},
     };
 
     impl Component for i32 {
@@ -421,6 +817,16 @@ mod tests {
         assert_eq!(*world.entity(entity).get::<i32>().unwrap(), 3);
     }
 
+    #[test]
+    fn insert_and_replace() {
+        let mut world = World::new();
+        let mut entity = world.spawn();
+
+        assert_eq!(entity.insert_and_replace(2i32), None);
+        assert_eq!(entity.insert_and_replace(3i32), Some(2));
+        assert_eq!(*entity.get::<i32>().unwrap(), 3);
+    }
+
     #[test]
     fn despawn() {
         let mut world = World::new();
@@ -441,6 +847,68 @@ mod tests {
         assert!(!world.contains_entity(entity));
     }
 
+    #[test]
+    fn despawn_stale() {
+        let mut world = World::new();
+        let stale = world.spawn().entity();
+        world.despawn(stale);
+
+        let entity = world.spawn().insert(2i32).entity();
+        assert_eq!(entity.index(), stale.index());
+
+        assert!(!world.despawn(stale));
+        assert!(world.contains_entity(entity));
+        assert_eq!(world.get::<i32>(entity), Some(&2));
+    }
+
+    #[test]
+    fn spawn_reserved() {
+        let mut world = World::new();
+        let spawned = world.spawn_empty().entity();
+
+        let reserved = world.reserve_entity();
+        assert!(!world.contains_entity(reserved));
+
+        world.spawn_reserved(reserved).insert(2i32);
+        assert!(world.contains_entity(reserved));
+        assert_ne!(reserved, spawned);
+        assert_eq!(world.get::<i32>(reserved), Some(&2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn spawn_unreserved() {
+        let mut world = World::new();
+        let entity = world.spawn().entity();
+        world.despawn(entity);
+
+        world.spawn_reserved(entity);
+    }
+
+    #[test]
+    fn retire_exhausted_index() {
+        let mut world = World::new();
+
+        let entity = Entity::from_raw_parts(0, u32::MAX - 1);
+        world.get_or_spawn(entity);
+        assert!(world.despawn(entity));
+
+        let last = world.spawn().entity();
+        assert_eq!(last, Entity::from_raw_parts(0, u32::MAX));
+        assert!(world.despawn(last));
+        assert!(!world.despawn(last));
+
+        let next = world.spawn().entity();
+        assert_eq!(next.index(), 1);
+        assert!(!world.contains_entity(last));
+        assert_eq!(world.entities.len(), 1);
+
+        // the index can still be used explicitly
+        world.get_or_spawn(last);
+        assert!(world.contains_entity(last));
+        assert_eq!(world.entities.len(), 2);
+    }
+
     #[test]
     fn zst() {
         #[derive(Component, Debug, PartialEq)]
@@ -457,6 +925,47 @@ mod tests {
         assert_eq!(iter.next(), Some((b, &Zst, &2)));
     }
 
+    #[test]
+    fn tuple_bundle() {
+        #[derive(Component, Debug, PartialEq)]
+        struct Big<const N: usize>(usize);
+
+        let mut world = World::new();
+        let entity = world
+            .spawn()
+            .insert((
+                Big::<0>(0),
+                Big::<1>(1),
+                Big::<2>(2),
+                Big::<3>(3),
+                Big::<4>(4),
+                Big::<5>(5),
+                Big::<6>(6),
+                Big::<7>(7),
+                Big::<8>(8),
+                Big::<9>(9),
+                Big::<10>(10),
+                Big::<11>(11),
+                Big::<12>(12),
+                Big::<13>(13),
+                Big::<14>(14),
+            ))
+            .entity();
+
+        assert_eq!(world.get::<Big<0>>(entity), Some(&Big(0)));
+        assert_eq!(world.get::<Big<7>>(entity), Some(&Big(7)));
+        assert_eq!(world.get::<Big<14>>(entity), Some(&Big(14)));
+
+        let query = world.query::<(&Big<0>, &Big<3>, &Big<6>, &Big<9>, &Big<12>)>();
+        let items = query.iter(&world).collect::<Vec<_>>();
+        assert_eq!(items, vec![(&Big(0), &Big(3), &Big(6), &Big(9), &Big(12))]);
+
+        let removed = world.remove::<(Big<13>, Big<14>)>(entity);
+        assert_eq!(removed, Some((Big(13), Big(14))));
+        assert!(!world.contains::<Big<14>>(entity));
+        assert!(world.contains::<Big<12>>(entity));
+    }
+
     #[test]
     fn multiple_entities() {
         let mut world = World::new();
@@ -535,6 +1044,103 @@ mod tests {
         assert_eq!(iter.next().unwrap(), entity3);
     }
 
+    #[test]
+    fn query_iter_order() {
+        let mut world = World::new();
+
+        let entities = (0..6).map(|_| world.spawn().entity()).collect::<Vec<_>>();
+        for &entity in entities.iter().rev() {
+            world.entity_mut(entity).insert(entity.index() as i32);
+        }
+
+        // removing swaps the storage around, and despawning frees an index for reuse
+        world.remove::<i32>(entities[1]);
+        world.entity_mut(entities[1]).insert(1i32);
+        world.despawn(entities[2]);
+        let reused = world.spawn().insert(2i32).entity();
+        assert_eq!(reused.index(), 2);
+
+        let query = world.query::<&i32>();
+        let items = query.iter(&world).copied().collect::<Vec<_>>();
+        assert_eq!(items, vec![0, 1, 2, 3, 4, 5]);
+
+        let mut visited = Vec::new();
+        query.for_each_entity(&world, |entity, _| visited.push(entity.index()));
+        assert_eq!(visited, vec![0, 1, 2, 3, 4, 5]);
+
+        let int = world.init_component::<i32>();
+        let query = DynamicQuery::new(&world).read(int);
+        let entities = query.iter(&world).map(|item| item.entity().index());
+        assert_eq!(entities.collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn query_single() {
+        let mut world = World::new();
+        let mut query = world.query::<&mut i32>();
+        assert_eq!(query.get_single(&world), Err(QuerySingleError::NoEntities));
+
+        let entity = world.spawn().insert(1i32).entity();
+        *query.single_mut(&mut world) += 1;
+        assert_eq!(query.single(&world), &2);
+
+        world.spawn().insert(3i32);
+        let single = query.get_single_mut(&mut world).map(|item| *item);
+        assert_eq!(single, Err(QuerySingleError::MultipleEntities));
+
+        let query = world.query_filtered::<Entity, With<f32>>();
+        world.entity_mut(entity).insert(0.0f32);
+        assert_eq!(query.single(&world), entity);
+    }
+
+    #[test]
+    fn get_by_id() {
+        let mut world = World::new();
+        let entity = world.spawn().insert(1i32).entity();
+        let int = world.init_component::<i32>();
+        let float = world.init_component::<f32>();
+
+        let ptr = world.get_by_id(entity, int).unwrap();
+        assert_eq!(unsafe { *(ptr as *const i32) }, 1);
+        assert!(world.get_by_id(entity, float).is_none());
+
+        let last_change_tick = world.change_tick();
+        world.increment_change_tick();
+        *world.get_mut::<i32>(entity).unwrap() = 2;
+        assert_eq!(world.get::<i32>(entity), Some(&2));
+
+        let ticks = world.get_change_ticks::<i32>(entity).unwrap();
+        assert!(ticks.is_changed(last_change_tick, world.change_tick()));
+
+        world.despawn(entity);
+        assert!(world.get_by_id(entity, int).is_none());
+        assert!(world.get::<i32>(entity).is_none());
+    }
+
+    #[test]
+    fn query_get_many() {
+        let mut world = World::new();
+        let a = world.spawn().insert(1i32).entity();
+        let b = world.spawn().insert(2i32).entity();
+        let c = world.spawn().insert(0.0f32).entity();
+
+        let mut query = world.query::<&mut i32>();
+        let [mut x, mut y] = query.get_many_mut(&mut world, [a, b]).unwrap();
+        std::mem::swap(&mut *x, &mut *y);
+        assert_eq!(query.get_many(&world, [a, b]), Ok([&2, &1]));
+        assert_eq!(query.get_many(&world, [a, a]), Ok([&2, &2]));
+
+        let aliased = query.get_many_mut(&mut world, [a, b, a]).map(|_| ());
+        assert_eq!(aliased, Err(QueryEntityError::AliasedMutability(a)));
+
+        let unmatched = query.get_many_mut(&mut world, [a, c]).map(|_| ());
+        assert_eq!(unmatched, Err(QueryEntityError::QueryDoesNotMatch(c)));
+
+        world.despawn(b);
+        let missing = query.get_many(&world, [a, b]).map(|_| ());
+        assert_eq!(missing, Err(QueryEntityError::NoSuchEntity(b)));
+    }
+
     #[test]
     fn query_iter_filter() {
         let mut world = World::new();
@@ -567,6 +1173,114 @@ mod tests {
         assert!(iter.next().is_none());
     }
 
+    #[test]
+    fn query_or_arity() {
+        let mut world = World::new();
+
+        let entity1 = world.spawn().insert(1i32).entity();
+        let entity2 = world.spawn().insert(2i32).insert(0.0f32).entity();
+
+        let query = world.query_filtered::<Entity, Or<()>>();
+        assert_eq!(query.iter(&world).count(), 0);
+
+        let query = world.query_filtered::<Entity, Or<(With<f32>,)>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![entity2]);
+
+        let query = world.query_filtered::<Entity, Or<(Changed<i32>,)>>();
+        assert_eq!(
+            query.iter(&world).collect::<Vec<_>>(),
+            vec![entity1, entity2]
+        );
+
+        let query = world.query::<Option<Or<(Changed<i32>, Changed<f32>)>>>();
+        assert_eq!(query.get(&world, entity1), Some(Some(true)));
+        assert_eq!(query.get(&world, entity2), Some(Some(true)));
+
+        let query = world.query::<Option<Or<()>>>();
+        assert_eq!(query.get(&world, entity1), Some(None));
+    }
+
+    #[test]
+    fn sparse_components() {
+        #[derive(Component, Debug, PartialEq)]
+        #[component(storage = "SparseSet")]
+        struct Marker(u32);
+
+        let mut world = World::new();
+
+        let entity1 = world.spawn().insert(1i32).insert(Marker(1)).entity();
+        let entity2 = world.spawn().insert(2i32).entity();
+        let entity3 = world.spawn().insert(Marker(3)).entity();
+
+        let query = world.query::<(&i32, &Marker)>();
+        assert_eq!(
+            query.iter(&world).collect::<Vec<_>>(),
+            vec![(&1, &Marker(1))]
+        );
+
+        let query = world.query_filtered::<Entity, With<Marker>>();
+        assert_eq!(
+            query.iter(&world).collect::<Vec<_>>(),
+            vec![entity1, entity3]
+        );
+
+        let query = world.query_filtered::<&i32, Without<Marker>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![&2]);
+
+        world.clear_trackers();
+        world.entity_mut(entity2).insert(Marker(2));
+        world.get_mut::<Marker>(entity3).unwrap().0 = 30;
+
+        let query = world.query_filtered::<(Entity, &Marker), Changed<Marker>>();
+        assert_eq!(
+            query.iter(&world).collect::<Vec<_>>(),
+            vec![(entity2, &Marker(2)), (entity3, &Marker(30))]
+        );
+
+        assert_eq!(
+            world.entity_mut(entity1).remove::<Marker>(),
+            Some(Marker(1))
+        );
+        world.despawn(entity3);
+
+        let marker = world.init_component::<Marker>();
+        let query = DynamicQuery::new(&world).read(marker);
+        let items = query.iter(&world).map(|item| item.entity());
+        assert_eq!(items.collect::<Vec<_>>(), vec![entity2]);
+
+        let ptr = world.get_by_id(entity2, marker).unwrap();
+        assert_eq!(unsafe { &*(ptr as *const Marker) }, &Marker(2));
+        assert!(!world.contains::<Marker>(entity1));
+    }
+
+    #[test]
+    fn spawn_batch() {
+        let mut world = World::new();
+
+        let entities = world.spawn_batch((0..3).map(|i| (i, i as f32)));
+        assert_eq!(entities.len(), 3);
+
+        for (i, &entity) in entities.iter().enumerate() {
+            assert_eq!(world.get::<i32>(entity), Some(&(i as i32)));
+            assert_eq!(world.get::<f32>(entity), Some(&(i as f32)));
+        }
+
+        let spawned = Entity::from_raw_parts(10, 2);
+        world.insert_or_spawn_batch([(entities[1], 5i32), (spawned, 6i32)]);
+
+        assert_eq!(world.get::<i32>(entities[1]), Some(&5));
+        assert_eq!(world.get::<f32>(entities[1]), Some(&1.0));
+        assert_eq!(world.get::<i32>(spawned), Some(&6));
+        assert_eq!(world.entities().len(), 4);
+
+        // free indices are reused before new ones are allocated
+        world.despawn(entities[0]);
+        let batch = world.spawn_batch([true, false, true]);
+        assert_eq!(batch[0].index(), entities[0].index());
+        assert!(batch.iter().all(|&entity| world.contains_entity(entity)));
+        assert_eq!(world.entities().len(), 6);
+    }
+
     #[test]
     fn query_option() {
         let mut world = World::new();
@@ -585,6 +1299,52 @@ mod tests {
         assert!(iter.next().is_none());
     }
 
+    #[test]
+    fn iter_components() {
+        let mut world = World::new();
+        world.init_component::<i32>();
+        world.insert_resource(0u32);
+        world.init_component::<bool>();
+
+        let components = world.components();
+        let names = |iter: &mut dyn Iterator<Item = &crate::world::ComponentInfo>| {
+            iter.map(|info| info.name().to_string()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(&mut components.iter()), vec!["i32", "u32", "bool"]);
+        assert_eq!(
+            names(&mut components.iter_components()),
+            vec!["i32", "bool"]
+        );
+        assert_eq!(names(&mut components.iter_resources()), vec!["u32"]);
+    }
+
+    #[test]
+    fn marker_components() {
+        #[derive(Component)]
+        struct Marker;
+
+        let mut world = World::new();
+        let entities = (0..100)
+            .map(|i| world.spawn().insert(i).entity())
+            .collect::<Vec<_>>();
+
+        for entity in entities.iter().step_by(2) {
+            world.entity_mut(*entity).insert(Marker);
+        }
+
+        for entity in entities.iter().step_by(4) {
+            world.entity_mut(*entity).remove::<Marker>().unwrap();
+        }
+
+        let with = world.query_filtered::<&i32, With<Marker>>();
+        let without = world.query_filtered::<&i32, Without<Marker>>();
+
+        assert_eq!(with.iter(&world).count(), 25);
+        assert_eq!(without.iter(&world).count(), 75);
+        assert!(with.iter(&world).all(|i| i % 4 == 2));
+    }
+
     #[test]
     fn bundle() {
         #[derive(Bundle)]
@@ -607,4 +1367,382 @@ mod tests {
         assert_eq!(bundle.a, 2);
         assert_eq!(bundle.b, true);
     }
+
+    #[test]
+    fn component_hooks() {
+        #[derive(Component)]
+        #[component(on_insert = normalize, on_replace = count_replace)]
+        struct Direction(f32, f32);
+
+        fn normalize(world: &mut World, entity: Entity) {
+            let mut direction = world.get_mut::<Direction>(entity).unwrap();
+            let length = (direction.0 * direction.0 + direction.1 * direction.1).sqrt();
+            direction.0 /= length;
+            direction.1 /= length;
+        }
+
+        fn count_replace(world: &mut World, _entity: Entity) {
+            *world.resource_or_default::<u32>() += 1;
+        }
+
+        let mut world = World::new();
+        let mut entity = world.spawn();
+        entity.insert(Direction(3.0, 4.0));
+
+        let direction = entity.get::<Direction>().unwrap();
+        assert_eq!((direction.0, direction.1), (0.6, 0.8));
+
+        entity.insert(Direction(0.0, 2.0));
+
+        let direction = entity.get::<Direction>().unwrap();
+        assert_eq!((direction.0, direction.1), (0.0, 1.0));
+        assert_eq!(*world.resource::<u32>(), 1);
+    }
+
+    #[test]
+    fn nested_shared_iteration() {
+        let mut world = World::new();
+        world.spawn().insert(1i32);
+        world.spawn().insert(2i32);
+
+        let mut query = world.query::<&mut i32>();
+        for a in query.iter(&world) {
+            let sum = query.iter(&world).map(|b| *a + *b).sum::<i32>();
+            assert_eq!(sum, 3 + 2 * *a);
+        }
+
+        for mut a in query.iter_mut(&mut world) {
+            *a += 1;
+        }
+
+        assert_eq!(query.iter(&world).copied().sum::<i32>(), 5);
+    }
+
+    #[test]
+    fn optimize_storage() {
+        let mut world = World::new();
+
+        let entities = (0..64)
+            .map(|i| world.spawn().insert(i).entity())
+            .collect::<Vec<_>>();
+
+        for &entity in entities.iter().step_by(3) {
+            world.entity_mut(entity).remove::<i32>();
+        }
+
+        for &entity in entities.iter().step_by(6) {
+            world.entity_mut(entity).insert(-1i32);
+        }
+
+        world.optimize_storage();
+
+        for (i, &entity) in entities.iter().enumerate() {
+            let expected = match i {
+                _ if i % 6 == 0 => Some(-1),
+                _ if i % 3 == 0 => None,
+                _ => Some(i as i32),
+            };
+
+            assert_eq!(world.get::<i32>(entity).copied(), expected);
+        }
+
+        let query = world.query::<(Entity, &i32)>();
+        let values = query.iter(&world).map(|(_, &i)| i).collect::<Vec<_>>();
+        assert_eq!(values.len(), 53);
+        assert!(values.iter().all(|&i| i == -1 || i % 3 != 0));
+    }
+
+    #[test]
+    fn for_each_entity() {
+        let mut world = World::new();
+
+        for i in 0..8 {
+            let mut entity = world.spawn();
+            entity.insert(i);
+
+            if i % 2 == 0 {
+                entity.insert(true);
+            }
+        }
+
+        let mut query = world.query_filtered::<&mut i32, With<bool>>();
+        query.for_each_entity_mut(&mut world, |_, mut i| *i *= 10);
+
+        let mut pairs = Vec::new();
+        query.for_each_entity(&world, |entity, &i| pairs.push((entity, i)));
+
+        let tuple_query = world.query_filtered::<(Entity, &i32), With<bool>>();
+        let expected = tuple_query
+            .iter(&world)
+            .map(|(entity, &i)| (entity, i))
+            .collect::<Vec<_>>();
+
+        assert_eq!(pairs, expected);
+        assert_eq!(pairs.iter().map(|&(_, i)| i).sum::<i32>(), 120);
+    }
+
+    #[test]
+    fn to_entity_map() {
+        let mut world = World::new();
+
+        let entities = (0..8)
+            .map(|i| {
+                let mut entity = world.spawn();
+                entity.insert(i);
+
+                if i % 2 == 0 {
+                    entity.insert(true);
+                }
+
+                entity.entity()
+            })
+            .collect::<Vec<_>>();
+
+        let query = world.query_filtered::<&i32, With<bool>>();
+        let map = query.to_entity_map(&world, |&i| i * 10);
+
+        assert_eq!(map.len(), 4);
+        for (i, entity) in entities.into_iter().enumerate() {
+            if i % 2 == 0 {
+                assert_eq!(map[&entity], i as i32 * 10);
+            } else {
+                assert!(!map.contains_key(&entity));
+            }
+        }
+    }
+
+    #[test]
+    fn dynamic_query() {
+        let mut world = World::new();
+
+        for i in 0..6 {
+            let mut entity = world.spawn();
+            entity.insert(i).insert(i as f32 * 0.5);
+
+            if i % 3 == 0 {
+                entity.insert(true);
+            }
+        }
+
+        let int = world.init_component::<i32>();
+        let float = world.init_component::<f32>();
+        let boolean = world.init_component::<bool>();
+
+        let query = DynamicQuery::new(&world)
+            .read(float)
+            .read(int)
+            .without(boolean);
+        let items = query
+            .iter(&world)
+            .map(|item| unsafe {
+                let float = *(item.get(0) as *const f32);
+                let int = *(item.get(1) as *const i32);
+                (item.entity(), int, float)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(items.len(), 4);
+        for &(entity, int, float) in items.iter() {
+            assert_eq!(world.get::<i32>(entity), Some(&int));
+            assert_eq!(int as f32 * 0.5, float);
+            assert_ne!(int % 3, 0);
+        }
+
+        let first = world.query::<Entity>().iter(&world).next().unwrap();
+        let query = DynamicQuery::new(&world).read(int).with(boolean);
+        assert_eq!(query.iter(&world).count(), 2);
+
+        let item = query.get(&world, first).unwrap();
+        assert_eq!(unsafe { *(item.get(0) as *const i32) }, 0);
+        assert!(query.access().has_read(int));
+        assert!(!query.access().has_read(boolean));
+    }
+
+    #[test]
+    fn retain() {
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Component)]
+        struct Pooled;
+
+        impl Drop for Pooled {
+            fn drop(&mut self) {
+                DROPPED.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let mut world = World::new();
+        let a = world.spawn_with((1i32, 2.0f32, true, Pooled)).entity();
+        let b = world.spawn_with((3i32, 4.0f32, Pooled)).entity();
+
+        world.entity_mut(a).retain::<(i32,)>();
+
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+        assert_eq!(world.get::<i32>(a), Some(&1));
+        assert!(!world.contains::<f32>(a));
+        assert!(!world.contains::<bool>(a));
+        assert!(!world.contains::<Pooled>(a));
+
+        world.with_commands(|commands| {
+            commands.entity(b).retain::<(f32, Pooled)>();
+        });
+
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+        assert!(!world.contains::<i32>(b));
+        assert_eq!(world.get::<f32>(b), Some(&4.0));
+        assert!(world.contains::<Pooled>(b));
+    }
+
+    #[test]
+    fn query_update_for_world() {
+        let mut a = World::new();
+        a.spawn().insert(1i32).insert(true);
+        a.spawn().insert(2i32);
+
+        // register components in a different order, so the ids differ from `a`
+        let mut b = World::new();
+        b.spawn().insert(false).insert(3i32);
+        b.spawn().insert(0.5f32);
+
+        let mut query = a.query_filtered::<&i32, With<bool>>();
+        assert_eq!(query.iter(&a).copied().collect::<Vec<_>>(), vec![1]);
+
+        query.update_for_world(&mut b);
+        assert_eq!(query.iter(&b).copied().collect::<Vec<_>>(), vec![3]);
+
+        query.update_for_world(&mut a);
+        assert_eq!(query.iter(&a).copied().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn change_ticks() {
+        let mut world = World::new();
+        let entity = world.spawn().insert(0i32).entity();
+
+        let ticks = world.get_change_ticks::<i32>(entity).unwrap();
+        assert_eq!(ticks, ChangeTicks::new(world.change_tick()));
+
+        world.clear_trackers();
+        *world.get_mut::<i32>(entity).unwrap() += 1;
+
+        let ticks = world.get_change_ticks::<i32>(entity).unwrap();
+        assert!(ticks.changed() > ticks.added());
+        assert_eq!(
+            ticks,
+            ChangeTicks::from_ticks(ticks.added(), ticks.changed())
+        );
+
+        let (last_change_tick, change_tick) = (world.last_change_tick(), world.change_tick());
+        assert!(ticks.is_changed(last_change_tick, change_tick));
+        assert!(!ticks.is_added(last_change_tick, change_tick));
+
+        let mut restored = ChangeTicks::from_ticks(0, 0);
+        restored.set_added(ticks.changed());
+        assert!(restored.is_added(last_change_tick, change_tick));
+        assert!(!restored.is_changed(last_change_tick, change_tick));
+    }
+
+    #[test]
+    fn mut_filter_map_unchanged() {
+        #[derive(Component)]
+        enum Shape {
+            Circle(f32),
+            Square,
+        }
+
+        fn radius(shape: &mut Shape) -> Option<&mut f32> {
+            match shape {
+                Shape::Circle(radius) => Some(radius),
+                Shape::Square => None,
+            }
+        }
+
+        let mut world = World::new();
+        let circle = world.spawn().insert(Shape::Circle(1.0)).entity();
+        let square = world.spawn().insert(Shape::Square).entity();
+        world.clear_trackers();
+
+        let query = world.query_filtered::<Entity, Changed<Shape>>();
+
+        let shape = world.get_mut::<Shape>(square).unwrap();
+        assert!(shape.filter_map_unchanged(radius).is_none());
+
+        let shape = world.get_mut::<Shape>(circle).unwrap();
+        assert_eq!(*shape.filter_map_unchanged(radius).unwrap(), 1.0);
+        assert_eq!(query.iter(&world).count(), 0);
+
+        let shape = world.get_mut::<Shape>(circle).unwrap();
+        *shape.filter_map_unchanged(radius).unwrap() = 2.0;
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![circle]);
+    }
+
+    #[test]
+    fn change_ticks_without_schedule() {
+        let mut world = World::new();
+        let entity = world.spawn().insert(0i32).entity();
+
+        let query = world.query_filtered::<Entity, Changed<i32>>();
+        assert_eq!(query.iter(&world).count(), 1);
+
+        world.clear_trackers();
+        assert_eq!(query.iter(&world).count(), 0);
+        assert!(!world.maybe_check_change_ticks());
+
+        // advance the change tick past the wrap boundary, `clear_trackers` adds the last tick
+        for _ in 0..u32::MAX / CHECK_TICK_THRESHOLD + 1 {
+            world
+                .change_tick
+                .fetch_add(CHECK_TICK_THRESHOLD - 1, Ordering::AcqRel);
+            world.clear_trackers();
+
+            assert_eq!(query.iter(&world).count(), 0);
+        }
+
+        *world.get_mut::<i32>(entity).unwrap() += 1;
+        assert_eq!(query.iter(&world).count(), 1);
+
+        world.clear_trackers();
+        assert_eq!(query.iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn get_or_spawn_with_generation() {
+        let mut world = World::new();
+
+        // beyond the allocated entities
+        let far = Entity::from_raw_parts(4, 7);
+        assert_eq!(
+            world.get_or_spawn_with_generation(far).unwrap().entity(),
+            far
+        );
+        assert!(world.contains_entity(far));
+        assert_eq!(world.entities().len(), 1);
+
+        // the skipped indices are free
+        let pending = Entity::from_raw_parts(2, 3);
+        world.get_or_spawn_with_generation(pending).unwrap();
+        assert!(world.contains_entity(pending));
+        assert_eq!(world.entities().len(), 2);
+
+        // already live with the same generation
+        world.entity_mut(far).insert(1i32);
+        let entity = world.get_or_spawn_with_generation(far).unwrap();
+        assert_eq!(entity.get::<i32>(), Some(&1));
+
+        // already live with a different generation
+        let stale = Entity::from_raw_parts(4, 6);
+        let error = world.get_or_spawn_with_generation(stale).err();
+        assert_eq!(error, Some(SpawnError::IndexInUse(far)));
+        assert!(world.contains_entity(far));
+
+        // reserved, but not yet flushed
+        let reserved = world.reserve_entity();
+        let requested = Entity::from_raw_parts(reserved.index(), reserved.generation() + 1);
+        let error = world.get_or_spawn_with_generation(requested).err();
+        assert_eq!(error, Some(SpawnError::IndexInUse(reserved)));
+
+        let replaced = world.get_or_spawn(requested).entity();
+        assert_eq!(replaced, requested);
+        assert!(!world.contains_entity(reserved));
+    }
 }